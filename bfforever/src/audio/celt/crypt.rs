@@ -7,7 +7,7 @@ use aes::{
         KeyInit,
     }
 };
-use super::Celt;
+use super::{Celt, CeltError};
 
 const AES_KEY: [u8; 32] = [
     0x07, 0xc2, 0x30, 0x93, 0x4a, 0x52, 0xf1, 0x72,
@@ -18,8 +18,8 @@ const AES_KEY: [u8; 32] = [
 
 pub trait Crypt {
     fn is_encrypted(&self) -> bool;
-    fn decrypt(&mut self);
-    fn encrypt(&mut self);
+    fn decrypt(&mut self) -> Result<(), CeltError>;
+    fn encrypt(&mut self) -> Result<(), CeltError>;
 }
 
 impl Crypt for Celt {
@@ -27,33 +27,38 @@ impl Crypt for Celt {
         self.header.encrypted
     }
 
-    fn decrypt(&mut self) {
+    fn decrypt(&mut self) -> Result<(), CeltError> {
         if !self.is_encrypted() {
-            return;
+            return Ok(());
         }
 
-        // Decrypt data
-        let cipher = Aes256::new_from_slice(&AES_KEY).unwrap();
-        cipher.decrypt_padded::<NoPadding>(&mut self.data).unwrap();
+        // Decrypt data (must be whole blocks)
+        let cipher = Aes256::new(&AES_KEY.into());
+        cipher
+            .decrypt_padded::<NoPadding>(&mut self.data)
+            .map_err(|_| CeltError::TruncatedData)?;
 
         // Update value
         self.header.encrypted = false;
 
         // Update offsets
-        self.recompute_offsets();
+        self.recompute_offsets()
     }
 
-    fn encrypt(&mut self) {
+    fn encrypt(&mut self) -> Result<(), CeltError> {
         if self.is_encrypted() {
-            return;
+            return Ok(());
         }
 
-        // Encrypt data
+        // Encrypt data (must be whole blocks)
         let data_size = self.data.len();
-        let cipher = Aes256::new_from_slice(&AES_KEY).unwrap();
-        cipher.encrypt_padded::<NoPadding>(&mut self.data, data_size).unwrap();
+        let cipher = Aes256::new(&AES_KEY.into());
+        cipher
+            .encrypt_padded::<NoPadding>(&mut self.data, data_size)
+            .map_err(|_| CeltError::TruncatedData)?;
 
         // Update value
         self.header.encrypted = true;
+        Ok(())
     }
 }
\ No newline at end of file
@@ -1,42 +1,71 @@
+use audiopus::Channels;
 use audiopus::coder::Decoder;
-use audiopus::{Channels, SampleRate};
 use crate::audio::AudioDecoder;
-use super::{Celt, CeltHeader};
+use super::{Celt, CeltError, CeltHeader, Crypt, get_opus_sample_rate};
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CeltDecoderOptions {
+    pub raw: bool, // Keep encoder delay and end padding
+}
+
+impl Celt {
+    pub fn decode_with_options(&self, options: &CeltDecoderOptions) -> Result<Box<[i16]>, CeltError> {
+        if self.is_encrypted() {
+            return Err(CeltError::EncryptedData);
+        }
 
-impl AudioDecoder for Celt {
-    fn decode(&self) -> Box<[i16]> {
         let packets = self.get_raw_packets();
 
-        let CeltHeader { total_samples, frame_size, sample_rate, .. } = self.header;
+        let CeltHeader { total_samples, frame_size, look_ahead, .. } = self.header;
         let channels = self.get_channels() as usize;
         let calc_frame_size = (frame_size as u32 * channels as u32) as usize;
 
-        let mut samples = vec![0i16; (total_samples * channels as u32) as usize].into_boxed_slice();
+        let frame_count = self.get_frame_count(&packets)?;
 
-        let sample_rate = match sample_rate {
-             8000 => SampleRate::Hz8000,
-            12000 => SampleRate::Hz12000,
-            16000 => SampleRate::Hz16000,
-            24000 => SampleRate::Hz24000,
-            48000 => SampleRate::Hz48000,
-                _ => panic!("Unsupported sample rate of {}Hz", sample_rate), // TODO: Switch to result error?
-        };
+        // Trimmed output starts after encoder delay
+        let skip_size = look_ahead as usize * channels;
+        let samples_size = total_samples as usize * channels;
 
-        let channels = match channels {
-            1 => Channels::Mono,
-            2 => Channels::Stereo,
-            _ => Channels::Auto,
-        };
+        let total_size = frame_count * calc_frame_size;
+        let mut samples = vec![0i16; total_size];
 
-        let mut decoder = Decoder::new(sample_rate, channels).unwrap();
+        let mut decoder = self.create_decoder()?;
 
         for raw_packet in packets.iter() {
             let data_start = calc_frame_size * raw_packet.frame_offset;
 
             let buffer = &mut samples[data_start..(data_start + calc_frame_size)];
-            decoder.decode(Some(raw_packet.data), buffer, false).unwrap();
+            decoder
+                .decode(Some(raw_packet.data), buffer, false)
+                .map_err(|e| CeltError::DecoderError { opus_error: e })?;
         }
 
-        samples
+        if !options.raw {
+            samples.truncate(skip_size + samples_size);
+            samples.drain(..skip_size);
+        }
+
+        Ok(samples.into_boxed_slice())
+    }
+
+    pub(super) fn create_decoder(&self) -> Result<Decoder, CeltError> {
+        let sample_rate = get_opus_sample_rate(self.header.sample_rate as u32)?;
+
+        let channels = match self.get_channels() {
+            1 => Channels::Mono,
+            2 => Channels::Stereo,
+            _ => Channels::Auto,
+        };
+
+        Decoder::new(sample_rate, channels)
+            .map_err(|e| CeltError::DecoderError { opus_error: e })
+    }
+}
+
+impl AudioDecoder for Celt {
+    type Error = CeltError;
+
+    fn decode(&self) -> Result<Box<[i16]>, CeltError> {
+        self.decode_with_options(&CeltDecoderOptions::default())
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,282 @@
+use audiopus::{Application, Bitrate, Channels};
+use audiopus::coder::Encoder;
+use super::*;
+
+const MAX_RUN_COUNT: usize = 0x7FFF;   // 15-bit map count
+const SILENCE_PRE_ROLL: usize = 1;     // Encoded frames kept before end of silence
+
+#[derive(Clone, Copy, Debug)]
+pub struct CeltEncoderOptions {
+    pub encrypt: bool,
+    pub silence_threshold: Option<u16>, // Max amplitude of silent frames (None = always encode)
+}
+
+impl Default for CeltEncoderOptions {
+    fn default() -> CeltEncoderOptions {
+        CeltEncoderOptions {
+            encrypt: false,
+            silence_threshold: Some(0),
+        }
+    }
+}
+
+impl Celt {
+    pub fn encode(samples: &[i16], channels: u32, sample_rate: u32) -> Result<Celt, CeltError> {
+        Celt::encode_with_options(samples, channels, sample_rate, &CeltEncoderOptions::default())
+    }
+
+    /// Encodes interleaved samples (mono is upmixed to stereo)
+    pub fn encode_with_options(samples: &[i16], channels: u32, sample_rate: u32, options: &CeltEncoderOptions) -> Result<Celt, CeltError> {
+        let opus_sample_rate = get_opus_sample_rate(sample_rate)?;
+
+        let mut stereo_samples = match channels {
+            1 => samples
+                .iter()
+                .flat_map(|s| [*s, *s])
+                .collect::<Vec<_>>(),
+            2 => samples.to_vec(),
+            _ => return Err(CeltError::UnsupportedChannels { channels }),
+        };
+
+        let mut encoder = Encoder::new(opus_sample_rate, Channels::Stereo, Application::Audio)?;
+
+        let mut header = CeltHeader {
+            total_samples: (stereo_samples.len() / 2) as u32,
+            sample_rate: sample_rate as u16,
+            ..Default::default()
+        };
+
+        encoder.set_bitrate(Bitrate::BitsPerSecond(header.bitrate as i32))?;
+        header.look_ahead = encoder.lookahead()? as u16;
+
+        // Add frames to cover encoder delay
+        let frame_size = header.frame_size as usize * 2;
+        let frame_count = (header.total_samples as usize + header.look_ahead as usize).div_ceil(header.frame_size as usize);
+        stereo_samples.resize(frame_count * frame_size, 0);
+
+        let mut packet_buffer = [0u8; MAX_PACKET_SIZE];
+        let delay_size = header.look_ahead as usize * 2;
+
+        let packets = stereo_samples
+            .chunks(frame_size)
+            .enumerate()
+            .map(|(i, frame)| {
+                // Every frame is encoded to keep encoder state in sync
+                let size = encoder.encode(frame, &mut packet_buffer)?;
+
+                // Include previous samples still in encoder delay
+                let start = (i * frame_size).saturating_sub(delay_size);
+                let end = (i + 1) * frame_size;
+
+                let is_silent = options.silence_threshold
+                    .is_some_and(|threshold| is_silent(&stereo_samples[start..end], threshold));
+
+                Ok((packet_buffer[..size].to_vec(), is_silent))
+            })
+            .collect::<Result<Vec<_>, CeltError>>()?;
+
+        let frames = packets
+            .iter()
+            .enumerate()
+            .map(|(i, (packet, _))| {
+                // Keep silent frames leading into audio so decoder state can catch up
+                let end = (i + SILENCE_PRE_ROLL + 1).min(packets.len());
+
+                match packets[i..end].iter().all(|(_, is_silent)| *is_silent) {
+                    true => None,
+                    _ => Some(packet.to_owned()),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut celt = Celt::from_frames(header, &frames)?;
+
+        if options.encrypt {
+            celt.encrypt()?;
+        }
+
+        Ok(celt)
+    }
+
+    /// Creates celt from encoded frames (`None` = silent frame)
+    pub(super) fn from_frames(mut header: CeltHeader, frames: &[Option<Vec<u8>>]) -> Result<Celt, CeltError> {
+        let mut map = create_packet_map(frames);
+        let map_size = map.len();
+
+        // Map is padded to multiple of 4
+        map.resize(map_size.next_multiple_of(4), 0);
+
+        let mut packets = Vec::new();
+
+        for packet in frames.iter().flatten() {
+            let size = packet.len();
+
+            if size > MAX_PACKET_SIZE {
+                return Err(CeltError::PacketTooLarge { size, max: MAX_PACKET_SIZE });
+            }
+
+            packets.push(((size >> 8) & 0x0F) as u8);
+            packets.push((size & 0xFF) as u8);
+            packets.extend_from_slice(packet);
+        }
+
+        header.encrypted = false;
+        header.map_size = map_size as u32;
+        header.packets_start_offset = header.map_start_offset + map.len() as u32;
+        header.packets_size = packets.len() as u32;
+
+        // Data is padded to fit 16-byte blocks
+        let mut data = map;
+        data.extend(packets);
+        data.resize(data.len().next_multiple_of(16), 0);
+
+        let mut celt = Celt {
+            header,
+            data: data.into_boxed_slice(),
+            ..Default::default()
+        };
+
+        celt.recompute_offsets()?;
+        Ok(celt)
+    }
+}
+
+fn is_silent(samples: &[i16], threshold: u16) -> bool {
+    samples
+        .iter()
+        .all(|s| s.unsigned_abs() <= threshold)
+}
+
+fn create_packet_map(frames: &[Option<Vec<u8>>]) -> Vec<u8> {
+    let mut map = Vec::new();
+    let mut silence = true; // Map always starts with silence run
+    let mut frame_idx = 0;
+
+    while frame_idx < frames.len() {
+        let count = frames[frame_idx..]
+            .iter()
+            .take(MAX_RUN_COUNT)
+            .take_while(|f| f.is_none() == silence)
+            .count();
+
+        write_run_count(&mut map, count);
+
+        frame_idx += count;
+        silence = !silence;
+    }
+
+    map
+}
+
+fn write_run_count(map: &mut Vec<u8>, count: usize) {
+    if count < 0x80 {
+        map.push(count as u8);
+    } else {
+        // 2-byte count w/ 0x80 flag
+        map.push(((count >> 8) as u8) | 0x80);
+        map.push((count & 0xFF) as u8);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_frames(runs: &[(bool, usize)]) -> Vec<Option<Vec<u8>>> {
+        runs
+            .iter()
+            .flat_map(|(silent, count)| std::iter::repeat_n(*silent, *count))
+            .enumerate()
+            .map(|(i, silent)| match silent {
+                true => None,
+                _ => Some(vec![(i & 0xFF) as u8; 3]),
+            })
+            .collect()
+    }
+
+    fn get_packet_offsets(celt: &Celt) -> Vec<usize> {
+        celt.get_raw_packets()
+            .iter()
+            .map(|p| p.frame_offset)
+            .collect()
+    }
+
+    #[test]
+    fn packet_map_alternates_runs() {
+        assert_eq!(vec![2, 2, 1, 1], create_packet_map(&create_frames(&[(true, 2), (false, 2), (true, 1), (false, 1)])));
+
+        // Map always starts with silence run
+        assert_eq!(vec![0, 3], create_packet_map(&create_frames(&[(false, 3)])));
+        assert!(create_packet_map(&[]).is_empty());
+    }
+
+    #[test]
+    fn packet_map_uses_2_byte_counts() {
+        assert_eq!(vec![0x7F, 0x01], create_packet_map(&create_frames(&[(true, 0x7F), (false, 1)])));
+        assert_eq!(vec![0x80, 0x80, 0x01], create_packet_map(&create_frames(&[(true, 0x80), (false, 1)])));
+        assert_eq!(vec![0x00, 0x81, 0x23], create_packet_map(&create_frames(&[(false, 0x123)])));
+    }
+
+    #[test]
+    fn packet_map_splits_max_run_count() {
+        // Longer runs are split by empty run of opposite type
+        let frames = create_frames(&[(true, MAX_RUN_COUNT + 1), (false, 1)]);
+        assert_eq!(vec![0xFF, 0xFF, 0x00, 0x01, 0x01], create_packet_map(&frames));
+    }
+
+    #[test]
+    fn from_frames_offsets_match_map() {
+        let frames = create_frames(&[(true, 3), (false, 2), (true, MAX_RUN_COUNT + 2), (false, 0x90)]);
+        let celt = Celt::from_frames(CeltHeader::default(), &frames).unwrap();
+
+        let expected_offsets = frames
+            .iter()
+            .enumerate()
+            .filter_map(|(i, f)| f.as_ref().map(|_| i))
+            .collect::<Vec<_>>();
+
+        assert_eq!(expected_offsets, get_packet_offsets(&celt));
+
+        for (packet, frame) in celt.get_raw_packets().iter().zip(frames.iter().flatten()) {
+            assert_eq!(frame.as_slice(), packet.data);
+        }
+    }
+
+    #[test]
+    fn encode_skips_silent_frames() {
+        let frame_size = CeltHeader::default().frame_size as usize;
+
+        // 3 frames silence, 2 frames audio, 3 frames silence
+        let samples = (0..(frame_size * 8))
+            .map(|i| match (frame_size * 3..frame_size * 5).contains(&i) {
+                true => ((i % 100) * 200) as i16,
+                _ => 0,
+            })
+            .collect::<Vec<_>>();
+
+        let celt = Celt::encode(&samples, 1, 48000).unwrap();
+
+        // Frame before audio is kept as pre-roll, frame after audio still has encoder delay
+        assert_eq!(vec![3 - SILENCE_PRE_ROLL, 3, 4, 5], get_packet_offsets(&celt));
+
+        let options = CeltEncoderOptions { silence_threshold: None, ..Default::default() };
+        let celt = Celt::encode_with_options(&samples, 1, 48000, &options).unwrap();
+
+        let frame_count = celt.get_frame_count(&celt.get_raw_packets()).unwrap();
+        assert_eq!((0..frame_count).collect::<Vec<_>>(), get_packet_offsets(&celt));
+    }
+
+    #[test]
+    fn encode_silence_threshold() {
+        let frame_size = CeltHeader::default().frame_size as usize;
+        let samples = vec![4i16; frame_size * 4];
+
+        let options = CeltEncoderOptions { silence_threshold: Some(3), ..Default::default() };
+        let celt = Celt::encode_with_options(&samples, 1, 48000, &options).unwrap();
+        assert!(!get_packet_offsets(&celt).is_empty());
+
+        let options = CeltEncoderOptions { silence_threshold: Some(4), ..Default::default() };
+        let celt = Celt::encode_with_options(&samples, 1, 48000, &options).unwrap();
+        assert!(get_packet_offsets(&celt).is_empty());
+    }
+}
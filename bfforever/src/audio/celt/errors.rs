@@ -0,0 +1,83 @@
+use audiopus::Error as OpusError;
+use ogg::OggReadError;
+use thiserror::Error as ThisError;
+use std::io::{Error as IOError, ErrorKind};
+
+#[derive(Debug, ThisError)]
+pub enum CeltError {
+    #[error("Invalid magic of {magic:?}")]
+    InvalidMagic {
+        magic: [u8; 4]
+    },
+    #[error("Invalid celt header: {message}")]
+    InvalidHeader {
+        message: String
+    },
+    #[error("Unexpected end of celt data")]
+    TruncatedData,
+    #[error("Invalid packet map: {message}")]
+    InvalidPacketMap {
+        message: String
+    },
+    #[error("Packet size of {size} exceeds max of {max}")]
+    PacketTooLarge {
+        size: usize,
+        max: usize
+    },
+    #[error("Celt data is encrypted")]
+    EncryptedData,
+    #[error("Invalid opus header: {message}")]
+    InvalidOpusHeader {
+        message: String
+    },
+    #[error("Unsupported frame size of {frame_size} (expected {expected})")]
+    UnsupportedFrameSize {
+        frame_size: usize,
+        expected: usize
+    },
+    #[error("Unsupported sample rate of {sample_rate}Hz")]
+    UnsupportedSampleRate {
+        sample_rate: u32
+    },
+    #[error("Unsupported channel count of {channels}")]
+    UnsupportedChannels {
+        channels: u32
+    },
+    #[error("Opus Error: {opus_error}")]
+    OpusError {
+        opus_error: OpusError
+    },
+    #[error("Decoder Error: {opus_error}")]
+    DecoderError {
+        opus_error: OpusError
+    },
+    #[error("Ogg Error: {ogg_error}")]
+    OggError {
+        ogg_error: OggReadError
+    },
+    #[error("IO Error: {io_error}")]
+    IOError {
+        io_error: IOError
+    },
+}
+
+impl From<IOError> for CeltError {
+    fn from(err: IOError) -> Self {
+        match err.kind() {
+            ErrorKind::UnexpectedEof => CeltError::TruncatedData,
+            _ => CeltError::IOError { io_error: err },
+        }
+    }
+}
+
+impl From<OggReadError> for CeltError {
+    fn from(err: OggReadError) -> Self {
+        CeltError::OggError { ogg_error: err }
+    }
+}
+
+impl From<OpusError> for CeltError {
+    fn from(err: OpusError) -> Self {
+        CeltError::OpusError { opus_error: err }
+    }
+}
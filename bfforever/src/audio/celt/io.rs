@@ -4,10 +4,10 @@ use nom::bytes::streaming::tag;
 use nom::number::streaming::{le_u16, le_u32};
 use nom::sequence::tuple;
 use std::fs::File;
-use std::io::{Read, Write};
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 use std::mem::size_of;
 use std::path::Path;
-use super::{Celt, CeltHeader, Crypt};
+use super::{Celt, CeltError, CeltHeader, Crypt};
 
 pub struct ByteWriter<'a>(&'a mut [u8]);
 
@@ -31,13 +31,13 @@ impl<'a> ByteWriter<'a> {
     }
 }
 
-pub trait IOFile {
-    fn open<T: AsRef<Path>>(celt_path: T) -> Self;
-    fn save<T: AsRef<Path>>(&self, celt_path: T);
+pub trait IOFile : Sized {
+    fn open<T: AsRef<Path>>(celt_path: T) -> Result<Self, CeltError>;
+    fn save<T: AsRef<Path>>(&self, celt_path: T) -> Result<(), CeltError>;
 }
 
 impl CeltHeader {
-    pub fn parse_data<'a>(data: &'a [u8]) -> IResult<&'a [u8], CeltHeader> {
+    pub fn parse_data(data: &[u8]) -> IResult<&[u8], CeltHeader> {
         let (d, _) = tag("BFAD")(data)?;
         let (d, (
             version,
@@ -104,29 +104,26 @@ impl CeltHeader {
     }
 }
 
-impl IOFile for Celt {
-    fn open<T: AsRef<Path>>(celt_path: T) -> Celt {
-        let mut celt_file = File::open(celt_path).unwrap();
-        //let file_size = celt_file.metadata().unwrap().len();
-        // TODO: Check file size before reading?
-
+impl Celt {
+    pub fn from_stream<T: Read + Seek>(stream: &mut T) -> Result<Celt, CeltError> {
         let mut header_data = [0u8; 40];
-        celt_file.read_exact(&mut header_data).unwrap();
+        stream.read_exact(&mut header_data)?;
 
-        let header = CeltHeader::from_data(&header_data);
+        let header = CeltHeader::from_data(&header_data)?;
+        let data_size = header.get_data_size()?;
 
-        let actual_map_size = header.packets_start_offset - header.map_start_offset; // Multiple of 4
-        let mut data_size = (actual_map_size + header.packets_size) as usize;
-        let rem = data_size % 16;
+        // Check size before allocating
+        let data_start = stream.stream_position()?;
+        let stream_end = stream.seek(SeekFrom::End(0))?;
+        stream.seek(SeekFrom::Start(data_start))?;
 
-        if rem > 0 {
-            // Pad size to fit 16-byte block
-            data_size += 16 - rem;
+        if stream_end.saturating_sub(data_start) < data_size as u64 {
+            return Err(CeltError::TruncatedData);
         }
 
         // Read data
         let mut data = vec![0u8; data_size].into_boxed_slice();
-        celt_file.read_exact(&mut data).unwrap();
+        stream.read_exact(&mut data)?;
 
         let mut celt = Celt {
             header,
@@ -135,23 +132,105 @@ impl IOFile for Celt {
         };
 
         if !celt.is_encrypted() {
-            celt.recompute_offsets();
+            celt.recompute_offsets()?;
         }
 
-        celt
+        Ok(celt)
     }
 
-    fn save<T: AsRef<Path>>(&self, celt_path: T) {
-        let celt_path = celt_path.as_ref();
-
-        let mut celt_file = create_new_file(celt_path).unwrap();
+    pub fn from_bytes(data: &[u8]) -> Result<Celt, CeltError> {
+        let mut cursor = Cursor::new(data);
+        Celt::from_stream(&mut cursor)
+    }
 
+    pub fn to_writer<T: Write>(&self, writer: &mut T) -> Result<(), CeltError> {
         let mut header_data = [0u8; 40];
         self.header.write_to_slice(&mut header_data);
 
-        celt_file.write(&header_data).unwrap();
-        celt_file.write(&self.data).unwrap();
+        writer.write_all(&header_data)?;
+        writer.write_all(&self.data)?;
+
+        Ok(())
+    }
+}
+
+impl IOFile for Celt {
+    fn open<T: AsRef<Path>>(celt_path: T) -> Result<Celt, CeltError> {
+        let mut celt_file = File::open(celt_path)?;
+        Celt::from_stream(&mut celt_file)
+    }
+
+    fn save<T: AsRef<Path>>(&self, celt_path: T) -> Result<(), CeltError> {
+        let mut celt_file = create_new_file(celt_path)?;
+        self.to_writer(&mut celt_file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::{AudioDecoder, CeltEncoderOptions};
+
+    fn create_header_data(update: impl FnOnce(&mut CeltHeader)) -> Vec<u8> {
+        let mut header = CeltHeader::default();
+        update(&mut header);
+
+        let mut header_data = [0u8; 40];
+        header.write_to_slice(&mut header_data);
+        header_data.to_vec()
+    }
+
+    #[test]
+    fn from_bytes_rejects_oversized_packets() {
+        let data = create_header_data(|h| h.packets_size = u32::MAX);
+        assert!(matches!(Celt::from_bytes(&data), Err(CeltError::TruncatedData)));
+    }
+
+    #[test]
+    fn from_bytes_rejects_zero_frame_size() {
+        let data = create_header_data(|h| h.frame_size = 0);
+        assert!(matches!(Celt::from_bytes(&data), Err(CeltError::InvalidHeader { .. })));
+    }
+
+    #[test]
+    fn from_bytes_rejects_map_after_packets() {
+        let data = create_header_data(|h| h.map_start_offset = h.packets_start_offset + 4);
+        assert!(matches!(Celt::from_bytes(&data), Err(CeltError::InvalidPacketMap { .. })));
+    }
 
-        // TODO: Return result
+    #[test]
+    fn from_bytes_rejects_truncated_data() {
+        let mut data = create_header_data(|h| {
+            h.map_size = 2;
+            h.packets_start_offset = h.map_start_offset + 4;
+            h.packets_size = 100;
+        });
+        data.extend([0u8; 16]);
+
+        assert!(matches!(Celt::from_bytes(&data), Err(CeltError::TruncatedData)));
+        assert!(matches!(Celt::from_bytes(&data[..20]), Err(CeltError::TruncatedData)));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn round_trip_encrypted() {
+        let samples = (0..4800)
+            .map(|i| ((i % 100) * 200) as i16)
+            .collect::<Vec<_>>();
+
+        let options = CeltEncoderOptions { encrypt: true, ..Default::default() };
+        let celt = Celt::encode_with_options(&samples, 1, 48000, &options).unwrap();
+
+        let mut data = Vec::new();
+        celt.to_writer(&mut data).unwrap();
+
+        let mut read_celt = Celt::from_bytes(&data).unwrap();
+        assert!(read_celt.is_encrypted());
+
+        // Packets can't be read until decrypted
+        assert!(matches!(read_celt.decode(), Err(CeltError::EncryptedData)));
+        assert!(matches!(read_celt.stream_decoder(1024), Err(CeltError::EncryptedData)));
+
+        read_celt.decrypt().unwrap();
+        assert_eq!(4800, read_celt.decode().unwrap().len() / 2);
+    }
+}
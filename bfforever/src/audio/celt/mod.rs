@@ -1,10 +1,20 @@
 mod crypt;
 mod decode;
+mod encode;
+mod errors;
 mod io;
+mod opus;
+mod stream;
 
+use audiopus::SampleRate;
 pub use crypt::*;
 #[allow(unused_imports)] pub use decode::*;
+pub use encode::*;
+pub use errors::*;
 pub use io::IOFile;
+pub use stream::*;
+
+const MAX_PACKET_SIZE: usize = 0x0FFF; // 12-bit size prefix
 
 #[derive(Clone, Copy)]
 struct CeltHeader {
@@ -30,6 +40,17 @@ struct PacketInfo {
     size: usize,
 }
 
+pub(crate) fn get_opus_sample_rate(sample_rate: u32) -> Result<SampleRate, CeltError> {
+    match sample_rate {
+         8000 => Ok(SampleRate::Hz8000),
+        12000 => Ok(SampleRate::Hz12000),
+        16000 => Ok(SampleRate::Hz16000),
+        24000 => Ok(SampleRate::Hz24000),
+        48000 => Ok(SampleRate::Hz48000),
+            _ => Err(CeltError::UnsupportedSampleRate { sample_rate }),
+    }
+}
+
 pub(crate) struct RawPacket<'a> {
     frame_offset: usize,
     data: &'a[u8],
@@ -43,10 +64,45 @@ pub struct Celt {
 }
 
 impl CeltHeader {
-    fn from_data(data: &[u8]) -> CeltHeader {
-        // TODO: Handle errors
-        let (_, header) = CeltHeader::parse_data(data).unwrap();
-        header
+    fn from_data(data: &[u8]) -> Result<CeltHeader, CeltError> {
+        let header = match CeltHeader::parse_data(data) {
+            Ok((_, header)) => header,
+            Err(nom::Err::Incomplete(_)) => return Err(CeltError::TruncatedData),
+            Err(_) => {
+                let mut magic = [0u8; 4];
+                magic.copy_from_slice(&data[..4]);
+
+                return Err(CeltError::InvalidMagic { magic });
+            }
+        };
+
+        if header.frame_size == 0 {
+            return Err(CeltError::InvalidHeader { message: String::from("Frame size of 0") });
+        }
+
+        if header.map_size > header.get_actual_map_size()? {
+            return Err(CeltError::InvalidPacketMap { message: format!("Map size of {} overlaps packets", header.map_size) });
+        }
+
+        header.get_data_size()?;
+        Ok(header)
+    }
+
+    /// Gets size of map including padding (multiple of 4)
+    fn get_actual_map_size(&self) -> Result<u32, CeltError> {
+        self.packets_start_offset
+            .checked_sub(self.map_start_offset)
+            .ok_or(CeltError::InvalidPacketMap { message: String::from("Packets start before packet map") })
+    }
+
+    /// Gets size of data following header (map + packets, padded to 16-byte block)
+    fn get_data_size(&self) -> Result<usize, CeltError> {
+        let data_size = (self.get_actual_map_size()? as usize)
+            .checked_add(self.packets_size as usize)
+            .and_then(|size| size.checked_next_multiple_of(16))
+            .ok_or(CeltError::InvalidPacketMap { message: format!("Packets size of {} is out of range", self.packets_size) })?;
+
+        Ok(data_size)
     }
 }
 
@@ -90,11 +146,16 @@ impl Celt {
         self.header.sample_rate
     }
 
-    pub(crate) fn recompute_offsets(&mut self) {
+    pub(crate) fn recompute_offsets(&mut self) -> Result<(), CeltError> {
         self.packet_map.clear();
 
-        let actual_map_size = self.header.packets_start_offset - self.header.map_start_offset; // Multiple of 4
-        let (map_data, packet_data) = self.data.split_at(actual_map_size as usize);
+        let actual_map_size = self.header.get_actual_map_size()? as usize;
+
+        if actual_map_size > self.data.len() {
+            return Err(CeltError::TruncatedData);
+        }
+
+        let (map_data, packet_data) = self.data.split_at(actual_map_size);
 
         let mut frame_idx = 0;
         let mut prev_count_part = None;
@@ -133,9 +194,11 @@ impl Celt {
         let mut data_index = 0;
 
         for (frame_start, num_packets) in map.iter() {
-            let mut frame_index = *frame_start;
+            for frame_index in *frame_start..(*frame_start + *num_packets) {
+                if data_index + 2 > packet_data.len() {
+                    return Err(CeltError::InvalidPacketMap { message: format!("Packet for frame {frame_index} is out of range") });
+                }
 
-            for _ in 0..*num_packets {
                 let packet_size = (((packet_data[data_index] & 0x0F) as usize) << 8) | packet_data[data_index + 1] as usize;
 
                 self.packet_map.push(PacketInfo {
@@ -145,12 +208,18 @@ impl Celt {
                 });
 
                 data_index += packet_size + 2;
-                frame_index += 1;
+
+                if data_index > packet_data.len() {
+                    return Err(CeltError::InvalidPacketMap { message: format!("Packet size of {packet_size} for frame {frame_index} is out of range") });
+                }
             }
         }
+
+        Ok(())
     }
 
-    pub(crate) fn get_raw_packets<'a>(&'a self) -> Vec<RawPacket<'a>> {
+    pub(crate) fn get_raw_packets(&self) -> Vec<RawPacket<'_>> {
+        // Offsets were validated when packet map was computed
         let actual_map_size = self.header.packets_start_offset - self.header.map_start_offset; // Multiple of 4
         let (_, packet_data) = self.data.split_at(actual_map_size as usize);
 
@@ -164,4 +233,22 @@ impl Celt {
             })
             .collect()
     }
-}
\ No newline at end of file
+
+    /// Gets frame count, silence runs can extend past last packet
+    pub(crate) fn get_frame_count(&self, packets: &[RawPacket]) -> Result<usize, CeltError> {
+        let CeltHeader { total_samples, frame_size, look_ahead, .. } = self.header;
+
+        if frame_size == 0 {
+            return Err(CeltError::InvalidHeader { message: String::from("Frame size of 0") });
+        }
+
+        let frame_count = packets
+            .iter()
+            .map(|p| p.frame_offset + 1)
+            .max()
+            .unwrap_or_default()
+            .max((look_ahead as usize + total_samples as usize).div_ceil(frame_size as usize));
+
+        Ok(frame_count)
+    }
+}
@@ -0,0 +1,247 @@
+use audiopus::{Application, Channels, SampleRate};
+use audiopus::coder::Encoder;
+use audiopus::packet::nb_samples;
+use crate::io::create_new_file;
+use ogg::reading::PacketReader;
+use ogg::writing::{PacketWriteEndInfo, PacketWriter};
+use std::fs::File;
+use std::io::{Read, Seek, Write};
+use std::path::Path;
+use super::*;
+
+const OPUS_GRANULE_RATE: u64 = 48000; // Granule positions are always 48kHz
+const OPUS_VENDOR: &str = "bfforever";
+const OPUS_HEAD_SIZE: usize = 19;
+
+const OGG_SERIAL: u32 = 0x42464144; // "BFAD"
+const PACKETS_PER_PAGE: usize = 50;
+
+impl Celt {
+    pub fn open_ogg_opus<T: AsRef<Path>>(ogg_path: T) -> Result<Celt, CeltError> {
+        let mut ogg_file = File::open(ogg_path)?;
+        Celt::from_ogg_opus(&mut ogg_file)
+    }
+
+    /// Reads packets from ogg opus stream (no re-encoding)
+    pub fn from_ogg_opus<T: Read + Seek>(reader: &mut T) -> Result<Celt, CeltError> {
+        let mut packet_reader = PacketReader::new(reader);
+
+        let head = packet_reader.read_packet_expected()?;
+        let serial = head.stream_serial();
+        let pre_skip = parse_opus_head(&head.data)?;
+
+        let tags = packet_reader.read_packet_expected()?;
+
+        if !tags.data.starts_with(b"OpusTags") {
+            return Err(CeltError::InvalidOpusHeader { message: String::from("Missing OpusTags packet") });
+        }
+
+        // Opus is always decoded at 48kHz
+        let mut header = CeltHeader {
+            look_ahead: pre_skip,
+            sample_rate: OPUS_GRANULE_RATE as u16,
+            ..Default::default()
+        };
+
+        let frame_size = header.frame_size as usize;
+        let mut frames = Vec::new();
+        let mut end_granule = 0;
+
+        while let Some(packet) = packet_reader.read_packet()? {
+            if packet.stream_serial() != serial {
+                // Ignore other logical streams
+                continue;
+            }
+
+            let packet_frame_size = nb_samples(&packet.data, SampleRate::Hz48000)?;
+
+            if packet_frame_size != frame_size {
+                return Err(CeltError::UnsupportedFrameSize { frame_size: packet_frame_size, expected: frame_size });
+            }
+
+            end_granule = packet.absgp_page();
+            frames.push(Some(packet.data));
+        }
+
+        // Final granule excludes pre-skip and end padding
+        let decoded_samples = (frames.len() * frame_size).saturating_sub(pre_skip as usize);
+        header.total_samples = (end_granule.saturating_sub(pre_skip as u64) as usize).min(decoded_samples) as u32;
+
+        Celt::from_frames(header, &frames)
+    }
+
+    pub fn save_ogg_opus<T: AsRef<Path>>(&self, ogg_path: T) -> Result<(), CeltError> {
+        let mut ogg_file = create_new_file(ogg_path)?;
+        self.to_ogg_opus(&mut ogg_file)
+    }
+
+    /// Writes packets as ogg opus stream (no re-encoding)
+    pub fn to_ogg_opus<T: Write>(&self, writer: &mut T) -> Result<(), CeltError> {
+        if self.is_encrypted() {
+            return Err(CeltError::EncryptedData);
+        }
+
+        let CeltHeader { total_samples, frame_size, look_ahead, sample_rate, .. } = self.header;
+        let opus_sample_rate = get_opus_sample_rate(sample_rate as u32)?;
+        let frame_size = frame_size as usize;
+
+        let to_granule = |samples: usize| (samples as u64 * OPUS_GRANULE_RATE) / sample_rate as u64;
+        let pre_skip = to_granule(look_ahead as usize);
+
+        let packets = self.get_raw_packets();
+
+        // Include trailing silence
+        let frame_count = self.get_frame_count(&packets)?;
+
+        // Final granule trims padding of last frame
+        let end_granule = (pre_skip + to_granule(total_samples as usize))
+            .min(to_granule(frame_count * frame_size));
+
+        // Silence runs are filled to keep granule positions continuous
+        let silence_packet = create_silence_packet(opus_sample_rate, frame_size)?;
+
+        let mut packet_writer = PacketWriter::new(writer);
+
+        packet_writer.write_packet(create_opus_head(self.get_channels() as u8, pre_skip as u16, sample_rate as u32), OGG_SERIAL, PacketWriteEndInfo::EndPage, 0)?;
+        packet_writer.write_packet(create_opus_tags(), OGG_SERIAL, PacketWriteEndInfo::EndPage, 0)?;
+
+        let mut packets = packets.iter().peekable();
+
+        for frame_idx in 0..frame_count {
+            let data = match packets.next_if(|p| p.frame_offset == frame_idx) {
+                Some(packet) => packet.data,
+                _ => &silence_packet,
+            };
+
+            let (end_info, granule) = match frame_idx + 1 {
+                i if i == frame_count => (PacketWriteEndInfo::EndStream, end_granule),
+                i if (i % PACKETS_PER_PAGE) == 0 => (PacketWriteEndInfo::EndPage, to_granule(i * frame_size)),
+                i => (PacketWriteEndInfo::NormalPacket, to_granule(i * frame_size)),
+            };
+
+            packet_writer.write_packet(data.into(), OGG_SERIAL, end_info, granule)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_opus_head(data: &[u8]) -> Result<u16, CeltError> {
+    if data.len() < OPUS_HEAD_SIZE || !data.starts_with(b"OpusHead") {
+        return Err(CeltError::InvalidOpusHeader { message: String::from("Missing OpusHead packet") });
+    }
+
+    let channels = data[9] as u32;
+    let pre_skip = u16::from_le_bytes([data[10], data[11]]);
+    let mapping_family = data[18];
+
+    if mapping_family != 0 {
+        return Err(CeltError::InvalidOpusHeader { message: format!("Unsupported channel mapping family of {mapping_family}") });
+    }
+
+    if channels == 0 || channels > 2 {
+        return Err(CeltError::UnsupportedChannels { channels });
+    }
+
+    Ok(pre_skip)
+}
+
+fn create_silence_packet(sample_rate: SampleRate, frame_size: usize) -> Result<Vec<u8>, CeltError> {
+    let encoder = Encoder::new(sample_rate, Channels::Stereo, Application::Audio)?;
+
+    let samples = vec![0i16; frame_size * 2];
+    let mut packet = [0u8; MAX_PACKET_SIZE];
+
+    let size = encoder.encode(&samples, &mut packet)?;
+    Ok(packet[..size].to_vec())
+}
+
+fn create_opus_head(channels: u8, pre_skip: u16, sample_rate: u32) -> Box<[u8]> {
+    let mut data = Vec::new();
+
+    data.extend_from_slice(b"OpusHead");
+    data.push(1); // Version
+    data.push(channels);
+    data.extend_from_slice(&pre_skip.to_le_bytes());
+    data.extend_from_slice(&sample_rate.to_le_bytes());
+    data.extend_from_slice(&0i16.to_le_bytes()); // Output gain
+    data.push(0); // Channel mapping family
+
+    data.into_boxed_slice()
+}
+
+fn create_opus_tags() -> Box<[u8]> {
+    let mut data = Vec::new();
+
+    data.extend_from_slice(b"OpusTags");
+    data.extend_from_slice(&(OPUS_VENDOR.len() as u32).to_le_bytes());
+    data.extend_from_slice(OPUS_VENDOR.as_bytes());
+    data.extend_from_slice(&0u32.to_le_bytes()); // Comment count
+
+    data.into_boxed_slice()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    fn write_ogg_opus(head: Box<[u8]>, packets: Vec<Vec<u8>>) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mut packet_writer = PacketWriter::new(&mut data);
+
+        packet_writer.write_packet(head, OGG_SERIAL, PacketWriteEndInfo::EndPage, 0).unwrap();
+        packet_writer.write_packet(create_opus_tags(), OGG_SERIAL, PacketWriteEndInfo::EndPage, 0).unwrap();
+
+        let count = packets.len();
+
+        for (i, packet) in packets.into_iter().enumerate() {
+            let end_info = match i + 1 {
+                n if n == count => PacketWriteEndInfo::EndStream,
+                _ => PacketWriteEndInfo::NormalPacket,
+            };
+
+            packet_writer.write_packet(packet.into_boxed_slice(), OGG_SERIAL, end_info, ((i + 1) * 960) as u64).unwrap();
+        }
+
+        data
+    }
+
+    #[test]
+    fn from_ogg_opus_rejects_mapping_family() {
+        let mut head = create_opus_head(2, 312, 48000);
+        head[18] = 1;
+
+        let data = write_ogg_opus(head, Vec::new());
+        let result = Celt::from_ogg_opus(&mut Cursor::new(data));
+
+        assert!(matches!(result, Err(CeltError::InvalidOpusHeader { .. })));
+    }
+
+    #[test]
+    fn from_ogg_opus_rejects_large_packet() {
+        // Code 0 packet (1 frame, 20ms celt)
+        let mut packet = vec![0u8; MAX_PACKET_SIZE + 1];
+        packet[0] = 0xFC;
+
+        let data = write_ogg_opus(create_opus_head(2, 312, 48000), vec![packet]);
+        let result = Celt::from_ogg_opus(&mut Cursor::new(data));
+
+        assert!(matches!(result, Err(CeltError::PacketTooLarge { size, max: MAX_PACKET_SIZE }) if size == MAX_PACKET_SIZE + 1));
+    }
+
+    #[test]
+    fn round_trip_ogg_opus() {
+        let samples = (0..9600)
+            .map(|i| ((i % 50) * 300) as i16)
+            .collect::<Vec<_>>();
+
+        let celt = Celt::encode(&samples, 2, 48000).unwrap();
+
+        let mut data = Vec::new();
+        celt.to_ogg_opus(&mut data).unwrap();
+
+        let read_celt = Celt::from_ogg_opus(&mut Cursor::new(data)).unwrap();
+        assert_eq!(celt.get_total_samples(), read_celt.get_total_samples());
+    }
+}
@@ -0,0 +1,278 @@
+use audiopus::coder::{Decoder, GenericCtl};
+use std::collections::VecDeque;
+use super::{Celt, CeltDecoderOptions, CeltError, Crypt, RawPacket};
+
+const SEEK_PRE_ROLL: usize = 4; // Frames decoded before seek position (opus recommends 80ms)
+
+/// Decodes celt audio in fixed-size blocks of interleaved samples
+pub struct CeltStreamDecoder<'a> {
+    decoder: Decoder,
+    packets: Vec<RawPacket<'a>>,
+    channels: usize,
+    frame_size: usize, // Samples per channel
+
+    block_size: usize, // Samples per channel
+    frame_count: usize,
+    output_start: usize, // Range of decoded samples to output (per channel)
+    output_end: usize,
+
+    frame_index: usize,  // Next frame to decode
+    packet_index: usize, // Next packet to decode
+    pending: VecDeque<i16>,
+    frame_buffer: Box<[i16]>, // Re-used for each decoded frame
+}
+
+impl Celt {
+    pub fn stream_decoder(&self, block_size: usize) -> Result<CeltStreamDecoder<'_>, CeltError> {
+        CeltStreamDecoder::new(self, block_size, &CeltDecoderOptions::default())
+    }
+}
+
+impl<'a> CeltStreamDecoder<'a> {
+    pub fn new(celt: &'a Celt, block_size: usize, options: &CeltDecoderOptions) -> Result<CeltStreamDecoder<'a>, CeltError> {
+        if celt.is_encrypted() {
+            return Err(CeltError::EncryptedData);
+        }
+
+        let packets = celt.get_raw_packets();
+
+        let total_samples = celt.header.total_samples as usize;
+        let look_ahead = celt.header.look_ahead as usize;
+        let frame_size = celt.header.frame_size as usize;
+
+        let frame_count = celt.get_frame_count(&packets)?;
+        let channels = celt.get_channels() as usize;
+
+        let (output_start, output_end) = match options.raw {
+            true => (0, frame_count * frame_size),
+            _ => (look_ahead, look_ahead + total_samples),
+        };
+
+        Ok(CeltStreamDecoder {
+            decoder: celt.create_decoder()?,
+            packets,
+            channels,
+            frame_size,
+
+            block_size: block_size.max(1),
+            frame_count,
+            output_start,
+            output_end,
+
+            frame_index: 0,
+            packet_index: 0,
+            pending: VecDeque::new(),
+            frame_buffer: vec![0i16; frame_size * channels].into_boxed_slice(),
+        })
+    }
+
+    pub fn get_frame_count(&self) -> usize {
+        self.frame_count
+    }
+
+    /// Gets next frame to be decoded
+    pub fn get_frame_index(&self) -> usize {
+        self.frame_index
+    }
+
+    /// Seeks to start of frame, discarding any pending samples
+    pub fn seek_to_frame(&mut self, frame_index: usize) -> Result<(), CeltError> {
+        let frame_index = frame_index.min(self.frame_count);
+
+        self.decoder
+            .reset_state()
+            .map_err(|e| CeltError::DecoderError { opus_error: e })?;
+
+        self.pending.clear();
+        self.frame_index = frame_index;
+        self.packet_index = self.packets.partition_point(|p| p.frame_offset < frame_index);
+
+        // Decode previous packets so decoder state can catch up
+        let pre_roll_start = self.packet_index.saturating_sub(SEEK_PRE_ROLL);
+
+        for packet in self.packets[pre_roll_start..self.packet_index].iter() {
+            if packet.frame_offset + SEEK_PRE_ROLL < frame_index {
+                continue;
+            }
+
+            self.decoder
+                .decode(Some(packet.data), &mut self.frame_buffer[..], false)
+                .map_err(|e| CeltError::DecoderError { opus_error: e })?;
+        }
+
+        Ok(())
+    }
+
+    /// Decodes next block into buffer, returns number of samples written (0 = end)
+    pub fn read_block(&mut self, block: &mut [i16]) -> Result<usize, CeltError> {
+        let block_size = (self.block_size * self.channels).min(block.len());
+
+        while self.pending.len() < block_size && self.frame_index < self.frame_count {
+            self.decode_next_frame()?;
+        }
+
+        // Last block can be smaller
+        let size = block_size.min(self.pending.len());
+
+        for (s, p) in block.iter_mut().zip(self.pending.drain(..size)) {
+            *s = p;
+        }
+
+        Ok(size)
+    }
+
+    fn decode_next_frame(&mut self) -> Result<(), CeltError> {
+        match self.packets.get(self.packet_index).filter(|p| p.frame_offset == self.frame_index) {
+            Some(packet) => {
+                self.decoder
+                    .decode(Some(packet.data), &mut self.frame_buffer[..], false)
+                    .map_err(|e| CeltError::DecoderError { opus_error: e })?;
+
+                self.packet_index += 1;
+            },
+            _ => self.frame_buffer.fill(0), // Silent frame
+        }
+
+        // Only keep samples within output range
+        let frame_start = self.frame_index * self.frame_size;
+        let start = self.output_start.clamp(frame_start, frame_start + self.frame_size) - frame_start;
+        let end = self.output_end.clamp(frame_start, frame_start + self.frame_size) - frame_start;
+
+        self.pending.extend(&self.frame_buffer[(start * self.channels)..(end * self.channels)]);
+        self.frame_index += 1;
+
+        Ok(())
+    }
+}
+
+impl Iterator for CeltStreamDecoder<'_> {
+    type Item = Result<Box<[i16]>, CeltError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut block = vec![0i16; self.block_size * self.channels];
+
+        match self.read_block(&mut block) {
+            Ok(0) => None,
+            Ok(size) => {
+                block.truncate(size);
+                Some(Ok(block.into_boxed_slice()))
+            },
+            Err(err) => Some(Err(err)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::AudioDecoder;
+
+    fn create_celt() -> Celt {
+        // Sine wave w/ silence gap to include silent frames
+        let samples = (0..(48000 * 2))
+            .map(|i| match (20000..50000).contains(&i) {
+                true => 0,
+                _ => ((i as f32 * 0.05).sin() * 8000.0) as i16,
+            })
+            .collect::<Vec<_>>();
+
+        Celt::encode(&samples, 1, 48000).unwrap()
+    }
+
+    #[test]
+    fn stream_matches_decode() {
+        let celt = create_celt();
+        let expected = celt.decode().unwrap();
+
+        let streamed = celt
+            .stream_decoder(1000)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+            .concat();
+
+        assert_eq!(&expected[..], &streamed[..]);
+    }
+
+    #[test]
+    fn read_block_reuses_buffer() {
+        let celt = create_celt();
+        let expected = celt.decode().unwrap();
+
+        let mut stream = celt.stream_decoder(700).unwrap();
+        let mut block = vec![0i16; 700 * 2];
+        let mut streamed = Vec::new();
+
+        loop {
+            match stream.read_block(&mut block).unwrap() {
+                0 => break,
+                size => streamed.extend_from_slice(&block[..size]),
+            }
+        }
+
+        assert_eq!(&expected[..], &streamed[..]);
+    }
+
+
+    fn decode_after_seek(celt: &Celt, frame_index: usize) -> (usize, Vec<i16>) {
+        let mut stream = CeltStreamDecoder::new(celt, 500, &CeltDecoderOptions { raw: true }).unwrap();
+
+        // Decode some blocks first so seeking has to discard pending samples
+        stream.next().unwrap().unwrap();
+        stream.seek_to_frame(frame_index).unwrap();
+
+        let frame_index = stream.get_frame_index();
+        let samples = stream.collect::<Result<Vec<_>, _>>().unwrap().concat();
+
+        (frame_index, samples)
+    }
+
+    #[test]
+    fn seek_matches_raw_decode() {
+        let celt = create_celt();
+        let raw = celt.decode_with_options(&CeltDecoderOptions { raw: true }).unwrap();
+        let frame_size = celt.get_frame_size() as usize * 2;
+
+        let packets = celt.get_raw_packets();
+        let silence_start = packets.windows(2).find(|p| p[0].frame_offset + 1 != p[1].frame_offset).unwrap()[0].frame_offset + 1;
+        let silence_end = packets.iter().map(|p| p.frame_offset).find(|f| *f > silence_start).unwrap();
+
+        // Pre-roll reaches start of stream so output is exact
+        let (_, samples) = decode_after_seek(&celt, SEEK_PRE_ROLL - 1);
+        assert_eq!(&raw[((SEEK_PRE_ROLL - 1) * frame_size)..], &samples[..]);
+
+        // Silent frames are exact, decoder state then converges with full decode
+        const CONVERGED_FRAMES: usize = 24;
+
+        for frame_index in [silence_start + 2, silence_end - 1, silence_end + SEEK_PRE_ROLL + 2] {
+            let (seek_index, samples) = decode_after_seek(&celt, frame_index);
+            assert_eq!(frame_index, seek_index);
+
+            let expected = &raw[(frame_index * frame_size)..];
+            assert_eq!(expected.len(), samples.len());
+
+            let silent_size = silence_end.saturating_sub(frame_index) * frame_size;
+            assert!(samples[..silent_size].iter().all(|s| *s == 0));
+
+            let converged_start = (silent_size + (CONVERGED_FRAMES * frame_size)).min(samples.len());
+            assert_eq!(&expected[converged_start..], &samples[converged_start..]);
+        }
+    }
+
+    #[test]
+    fn seek_past_end() {
+        let celt = create_celt();
+
+        let mut stream = celt.stream_decoder(500).unwrap();
+        let frame_count = stream.get_frame_count();
+
+        stream.seek_to_frame(frame_count + 10).unwrap();
+        assert_eq!(frame_count, stream.get_frame_index());
+        assert!(stream.next().is_none());
+
+        // Can seek back after reaching end
+        stream.seek_to_frame(0).unwrap();
+        let samples = stream.collect::<Result<Vec<_>, _>>().unwrap().concat();
+        assert_eq!(&celt.decode().unwrap()[..], &samples[..]);
+    }
+}
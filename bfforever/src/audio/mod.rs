@@ -6,7 +6,9 @@ use std::path::Path;
 pub use self::wav::*;
 
 pub trait AudioDecoder {
-    fn decode(&self) -> Box<[i16]>;
+    type Error;
+
+    fn decode(&self) -> Result<Box<[i16]>, Self::Error>;
 }
 
 pub trait AudioEncoder {
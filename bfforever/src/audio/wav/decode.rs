@@ -0,0 +1,80 @@
+use crate::audio::AudioDecoder;
+use super::WavError;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum WavSampleFormat {
+    UInt8,
+    Int16,
+    Int24,
+    Int32,
+    Float32,
+}
+
+pub struct WavDecoder {
+    pub(crate) format: WavSampleFormat,
+    pub(crate) channels: u16,
+    pub(crate) sample_rate: u32,
+    pub(crate) data: Vec<u8>,
+}
+
+impl WavSampleFormat {
+    pub fn get_sample_size(&self) -> usize {
+        match self {
+            WavSampleFormat::UInt8 => 1,
+            WavSampleFormat::Int16 => 2,
+            WavSampleFormat::Int24 => 3,
+            WavSampleFormat::Int32 | WavSampleFormat::Float32 => 4,
+        }
+    }
+
+    fn to_i16(self, b: &[u8]) -> i16 {
+        match self {
+            WavSampleFormat::UInt8 => ((b[0] as i16) - 128) << 8,
+            WavSampleFormat::Int16 => i16::from_le_bytes([b[0], b[1]]),
+            // Keep most significant bits
+            WavSampleFormat::Int24 => i16::from_le_bytes([b[1], b[2]]),
+            WavSampleFormat::Int32 => i16::from_le_bytes([b[2], b[3]]),
+            WavSampleFormat::Float32 => {
+                let value = f32::from_le_bytes([b[0], b[1], b[2], b[3]]);
+                (value * 32768.0).clamp(i16::MIN as f32, i16::MAX as f32) as i16
+            },
+        }
+    }
+}
+
+impl WavDecoder {
+    pub fn get_format(&self) -> WavSampleFormat {
+        self.format
+    }
+
+    pub fn get_channels(&self) -> u16 {
+        self.channels
+    }
+
+    pub fn get_sample_rate(&self) -> u32 {
+        self.sample_rate
+    }
+
+    /// Gets sample count per channel
+    pub fn get_total_samples(&self) -> u32 {
+        let frame_size = self.format.get_sample_size() * (self.channels.max(1) as usize);
+        (self.data.len() / frame_size) as u32
+    }
+}
+
+impl AudioDecoder for WavDecoder {
+    type Error = WavError;
+
+    fn decode(&self) -> Result<Box<[i16]>, WavError> {
+        let sample_size = self.format.get_sample_size();
+        let sample_count = self.get_total_samples() as usize * self.channels as usize;
+
+        let samples = self.data
+            .chunks_exact(sample_size)
+            .take(sample_count)
+            .map(|b| self.format.to_i16(b))
+            .collect();
+
+        Ok(samples)
+    }
+}
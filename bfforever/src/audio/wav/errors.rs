@@ -0,0 +1,40 @@
+use crate::riff::ReadRiffError;
+use thiserror::Error as ThisError;
+use std::io::Error as IOError;
+
+#[derive(Debug, ThisError)]
+pub enum WavError {
+    #[error("Invalid fourcc of {fourcc:?}")]
+    InvalidFourcc {
+        fourcc: Option<[u8; 4]>
+    },
+    #[error("Missing \"{id}\" chunk")]
+    MissingChunk {
+        id: String
+    },
+    #[error("Unsupported wav format 0x{format_tag:04X} with {bits_per_sample} bits per sample")]
+    UnsupportedFormat {
+        format_tag: u16,
+        bits_per_sample: u16
+    },
+    #[error("Riff Error: {riff_error}")]
+    RiffError {
+        riff_error: ReadRiffError
+    },
+    #[error("IO Error: {io_error}")]
+    IOError {
+        io_error: IOError
+    },
+}
+
+impl From<IOError> for WavError {
+    fn from(err: IOError) -> Self {
+        WavError::IOError { io_error: err }
+    }
+}
+
+impl From<ReadRiffError> for WavError {
+    fn from(err: ReadRiffError) -> Self {
+        WavError::RiffError { riff_error: err }
+    }
+}
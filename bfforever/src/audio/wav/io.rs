@@ -0,0 +1,211 @@
+use crate::io::{read_u16, read_u32};
+use crate::riff::RiffReader;
+use std::fs::File;
+use std::io::{Cursor, Read, Seek};
+use std::path::Path;
+use super::{WavDecoder, WavError, WavSampleFormat};
+
+const FOURCC_WAVE: &[u8; 4] = b"WAVE";
+const CHUNK_FORMAT: &[u8; 4] = b"fmt ";
+const CHUNK_DATA: &[u8; 4] = b"data";
+
+const WAVE_FORMAT_PCM: u16 = 0x0001;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 0x0003;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+impl WavDecoder {
+    pub fn from_file<T: AsRef<Path>>(wav_path: T) -> Result<WavDecoder, WavError> {
+        let mut file = File::open(wav_path)?;
+        WavDecoder::from_stream(&mut file)
+    }
+
+    pub fn from_stream<T: Read + Seek>(stream: &mut T) -> Result<WavDecoder, WavError> {
+        let mut riff = RiffReader::new(stream)?;
+
+        if riff.get_fourcc() != Some(FOURCC_WAVE) {
+            return Err(WavError::InvalidFourcc { fourcc: riff.get_fourcc().copied() });
+        }
+
+        let find_chunk = |id: &[u8; 4]| (0..riff.get_chunk_count())
+            .find(|i| riff.get_chunk_info(*i).is_some_and(|c| c.id.eq(id)))
+            .ok_or(WavError::MissingChunk { id: String::from_utf8_lossy(id).into_owned() });
+
+        let format_index = find_chunk(CHUNK_FORMAT)?;
+        let data_index = find_chunk(CHUNK_DATA)?;
+
+        let (format, channels, sample_rate) = parse_format(riff.read_chunk(format_index)?)?;
+        let data = riff.read_chunk(data_index)?.into_inner();
+
+        Ok(WavDecoder {
+            format,
+            channels,
+            sample_rate,
+            data,
+        })
+    }
+}
+
+fn parse_format(mut reader: Cursor<Vec<u8>>) -> Result<(WavSampleFormat, u16, u32), WavError> {
+    let mut format_tag = read_u16(&mut reader, false)?;
+    let channels = read_u16(&mut reader, false)?;
+    let sample_rate = read_u32(&mut reader, false)?;
+    let _byte_rate = read_u32(&mut reader, false)?;
+    let _block_align = read_u16(&mut reader, false)?;
+    let bits_per_sample = read_u16(&mut reader, false)?;
+
+    if format_tag == WAVE_FORMAT_EXTENSIBLE {
+        let _extra_size = read_u16(&mut reader, false)?;
+        let _valid_bits = read_u16(&mut reader, false)?;
+        let _channel_mask = read_u32(&mut reader, false)?;
+
+        // Sub format guid starts with actual format tag
+        format_tag = read_u16(&mut reader, false)?;
+    }
+
+    let format = match (format_tag, bits_per_sample) {
+        (WAVE_FORMAT_PCM, 8) => WavSampleFormat::UInt8,
+        (WAVE_FORMAT_PCM, 16) => WavSampleFormat::Int16,
+        (WAVE_FORMAT_PCM, 24) => WavSampleFormat::Int24,
+        (WAVE_FORMAT_PCM, 32) => WavSampleFormat::Int32,
+        (WAVE_FORMAT_IEEE_FLOAT, 32) => WavSampleFormat::Float32,
+        _ => return Err(WavError::UnsupportedFormat { format_tag, bits_per_sample }),
+    };
+
+    Ok((format, channels, sample_rate))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::AudioDecoder;
+    use crate::riff::RiffWriter;
+
+    fn create_format(format_tag: u16, channels: u16, sample_rate: u32, bits_per_sample: u16) -> Vec<u8> {
+        let block_align = channels * (bits_per_sample / 8);
+
+        [
+            &format_tag.to_le_bytes()[..],
+            &channels.to_le_bytes(),
+            &sample_rate.to_le_bytes(),
+            &(sample_rate * block_align as u32).to_le_bytes(),
+            &block_align.to_le_bytes(),
+            &bits_per_sample.to_le_bytes(),
+        ].concat()
+    }
+
+    fn create_extensible_format(sub_format: u16, channels: u16, sample_rate: u32, bits_per_sample: u16) -> Vec<u8> {
+        let mut format = create_format(WAVE_FORMAT_EXTENSIBLE, channels, sample_rate, bits_per_sample);
+        format.extend(22u16.to_le_bytes());
+        format.extend(bits_per_sample.to_le_bytes());
+        format.extend(0x3u32.to_le_bytes()); // Front left + right
+
+        // Sub format guid (KSDATAFORMAT_SUBTYPE_*)
+        format.extend(sub_format.to_le_bytes());
+        format.extend([0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71]);
+        format
+    }
+
+    fn create_wav(format: &[u8], data: &[u8]) -> WavDecoder {
+        let mut riff = RiffWriter::new(Cursor::new(Vec::new()), *FOURCC_WAVE, false).unwrap();
+        riff.write_chunk(CHUNK_FORMAT, format).unwrap();
+        riff.write_chunk(CHUNK_DATA, data).unwrap();
+
+        let mut stream = riff.finish().unwrap();
+        stream.set_position(0);
+
+        WavDecoder::from_stream(&mut stream).unwrap()
+    }
+
+    #[test]
+    fn decode_uint8() {
+        let wav = create_wav(&create_format(WAVE_FORMAT_PCM, 1, 22050, 8), &[0x00, 0x80, 0xFF]);
+
+        assert_eq!(WavSampleFormat::UInt8, wav.get_format());
+        assert_eq!(1, wav.get_channels());
+        assert_eq!(22050, wav.get_sample_rate());
+        assert_eq!(3, wav.get_total_samples());
+        assert_eq!(&[-32768, 0, 32512], &wav.decode().unwrap()[..]);
+    }
+
+    #[test]
+    fn decode_int16() {
+        let data = [0x34, 0x12, 0xFE, 0xFF, 0x00, 0x80, 0xFF, 0x7F];
+        let wav = create_wav(&create_format(WAVE_FORMAT_PCM, 2, 44100, 16), &data);
+
+        assert_eq!(WavSampleFormat::Int16, wav.get_format());
+        assert_eq!(2, wav.get_channels());
+        assert_eq!(44100, wav.get_sample_rate());
+        assert_eq!(2, wav.get_total_samples());
+        assert_eq!(&[0x1234, -2, i16::MIN, i16::MAX], &wav.decode().unwrap()[..]);
+    }
+
+    #[test]
+    fn decode_int24() {
+        let data = [0x56, 0x34, 0x12, 0xFF, 0xFF, 0xFF];
+        let wav = create_wav(&create_format(WAVE_FORMAT_PCM, 1, 48000, 24), &data);
+
+        assert_eq!(WavSampleFormat::Int24, wav.get_format());
+        assert_eq!(2, wav.get_total_samples());
+        assert_eq!(&[0x1234, -1], &wav.decode().unwrap()[..]);
+    }
+
+    #[test]
+    fn decode_int32() {
+        let data = [0x78, 0x56, 0x34, 0x12, 0x00, 0x00, 0x00, 0x80];
+        let wav = create_wav(&create_format(WAVE_FORMAT_PCM, 1, 48000, 32), &data);
+
+        assert_eq!(WavSampleFormat::Int32, wav.get_format());
+        assert_eq!(&[0x1234, i16::MIN], &wav.decode().unwrap()[..]);
+    }
+
+    #[test]
+    fn decode_float32_clamps() {
+        let data = [0.5f32, -1.0, 2.0, -2.0]
+            .iter()
+            .flat_map(|f| f.to_le_bytes())
+            .collect::<Vec<_>>();
+        let wav = create_wav(&create_format(WAVE_FORMAT_IEEE_FLOAT, 2, 48000, 32), &data);
+
+        assert_eq!(WavSampleFormat::Float32, wav.get_format());
+        assert_eq!(2, wav.get_total_samples());
+        assert_eq!(&[16384, i16::MIN, i16::MAX, i16::MIN], &wav.decode().unwrap()[..]);
+    }
+
+    #[test]
+    fn decode_extensible() {
+        let data = [0x34, 0x12, 0xFE, 0xFF];
+        let wav = create_wav(&create_extensible_format(WAVE_FORMAT_PCM, 2, 44100, 16), &data);
+
+        assert_eq!(WavSampleFormat::Int16, wav.get_format());
+        assert_eq!(2, wav.get_channels());
+        assert_eq!(44100, wav.get_sample_rate());
+        assert_eq!(&[0x1234, -2], &wav.decode().unwrap()[..]);
+
+        let data = 0.25f32.to_le_bytes();
+        let wav = create_wav(&create_extensible_format(WAVE_FORMAT_IEEE_FLOAT, 1, 48000, 32), &data);
+
+        assert_eq!(WavSampleFormat::Float32, wav.get_format());
+        assert_eq!(&[8192], &wav.decode().unwrap()[..]);
+    }
+
+    #[test]
+    fn decode_ignores_partial_frame() {
+        let wav = create_wav(&create_format(WAVE_FORMAT_PCM, 2, 44100, 16), &[0x01, 0x00, 0x02, 0x00, 0x03]);
+
+        assert_eq!(1, wav.get_total_samples());
+        assert_eq!(&[1, 2], &wav.decode().unwrap()[..]);
+    }
+
+    #[test]
+    fn from_stream_rejects_unsupported_format() {
+        let mut riff = RiffWriter::new(Cursor::new(Vec::new()), *FOURCC_WAVE, false).unwrap();
+        riff.write_chunk(CHUNK_FORMAT, &create_format(WAVE_FORMAT_PCM, 1, 44100, 12)).unwrap();
+        riff.write_chunk(CHUNK_DATA, &[]).unwrap();
+
+        let mut stream = riff.finish().unwrap();
+        stream.set_position(0);
+
+        let result = WavDecoder::from_stream(&mut stream);
+        assert!(matches!(result, Err(WavError::UnsupportedFormat { format_tag: WAVE_FORMAT_PCM, bits_per_sample: 12 })));
+    }
+}
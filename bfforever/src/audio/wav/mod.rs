@@ -1,5 +1,9 @@
+mod decode;
 mod encode;
+mod errors;
 mod io;
 
+pub use decode::*;
 pub use encode::*;
-#[allow(unused_imports)] pub(crate) use io::*;
\ No newline at end of file
+pub use errors::*;
+#[allow(unused_imports)] pub(crate) use io::*;
@@ -14,7 +14,7 @@ pub fn create_missing_dirs<T: AsRef<Path>>(file_path: T) -> IOResult<()> {
     // Create directory
     if let Some(output_dir) = dir {
         if !output_dir.exists() {
-            create_dir_all(&output_dir)?;
+            create_dir_all(output_dir)?;
         }
     }
 
@@ -3,7 +3,7 @@ mod zobject;
 
 pub use file::*;
 use std::fs::{create_dir_all, File, remove_file};
-use std::io::{Error as IOError, Read, Seek};
+use std::io::{Error as IOError, Read, Seek, Write};
 use std::path::Path;
 #[allow(unused_imports)] pub use zobject::*;
 
@@ -17,7 +17,7 @@ pub fn create_new_file<T: AsRef<Path>>(file_path: T) -> Result<File, IOError> {
     // Create directory
     if let Some(output_dir) = file_path.parent() {
         if !output_dir.exists() {
-            create_dir_all(&output_dir)?;
+            create_dir_all(output_dir)?;
         }
     }
 
@@ -32,7 +32,7 @@ pub fn create_new_file<T: AsRef<Path>>(file_path: T) -> Result<File, IOError> {
 
 pub fn read_u8<T: Read + Seek>(stream: &mut T) -> Result<u8, IOError> {
     let mut b = [0u8; std::mem::size_of::<u8>()];
-    stream.read(&mut b)?;
+    stream.read_exact(&mut b)?;
 
     Ok(b[0])
 }
@@ -51,6 +51,54 @@ pub fn read_u32_be<T: Read + Seek>(stream: &mut T) -> Result<u32, IOError> {
     Ok(u32::from_be_bytes(b))
 }
 
+pub fn read_u16<T: Read + Seek>(stream: &mut T, big_endian: bool) -> Result<u16, IOError> {
+    let mut b = [0u8; std::mem::size_of::<u16>()];
+    stream.read_exact(&mut b)?;
+
+    Ok(match big_endian {
+        true => u16::from_be_bytes(b),
+        _ => u16::from_le_bytes(b)
+    })
+}
+
+pub fn read_u32<T: Read + Seek>(stream: &mut T, big_endian: bool) -> Result<u32, IOError> {
+    let mut b = [0u8; std::mem::size_of::<u32>()];
+    stream.read_exact(&mut b)?;
+
+    Ok(match big_endian {
+        true => u32::from_be_bytes(b),
+        _ => u32::from_le_bytes(b)
+    })
+}
+
+pub fn read_u64<T: Read + Seek>(stream: &mut T, big_endian: bool) -> Result<u64, IOError> {
+    let mut b = [0u8; std::mem::size_of::<u64>()];
+    stream.read_exact(&mut b)?;
+
+    Ok(match big_endian {
+        true => u64::from_be_bytes(b),
+        _ => u64::from_le_bytes(b)
+    })
+}
+
+pub fn write_u32<T: Seek + Write>(stream: &mut T, value: u32, big_endian: bool) -> Result<(), IOError> {
+    let b = match big_endian {
+        true => value.to_be_bytes(),
+        _ => value.to_le_bytes()
+    };
+
+    stream.write_all(&b)
+}
+
+pub fn write_u64<T: Seek + Write>(stream: &mut T, value: u64, big_endian: bool) -> Result<(), IOError> {
+    let b = match big_endian {
+        true => value.to_be_bytes(),
+        _ => value.to_le_bytes()
+    };
+
+    stream.write_all(&b)
+}
+
 pub fn read_terminated_string_with_size<T: Read + Seek>(stream: &mut T, n: usize) -> Result<String, IOError> {
     let mut str_buffer = vec![0u8; n];
     stream.read_exact(&mut str_buffer)?;
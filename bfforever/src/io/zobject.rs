@@ -1,16 +1,401 @@
 use crate::{HKey, StringKey, SKey};
-use std::io::{Error as IOError, Read, Seek, Write};
+use std::io::{Error as IOError, ErrorKind, Read, Seek, Write};
+use super::{read_u32, read_u64, write_u32, write_u64};
+
+pub const ZOBJECT_LATEST_VERSION: u32 = u32::MAX;
+
+/// Options used when reading/writing zobject data
+#[derive(Clone, Copy, Debug)]
+pub struct ZObjectContext {
+    pub big_endian: bool,
+    pub version: u32, // Fields marked with since_version are only read/written at or above this
+}
+
+impl ZObjectContext {
+    pub fn new(big_endian: bool) -> ZObjectContext {
+        ZObjectContext {
+            big_endian,
+            version: ZOBJECT_LATEST_VERSION,
+        }
+    }
+
+    pub fn with_big_endian(&self) -> ZObjectContext {
+        ZObjectContext {
+            big_endian: true,
+            ..*self
+        }
+    }
+
+    pub fn with_version(&self, version: u32) -> ZObjectContext {
+        ZObjectContext {
+            version,
+            ..*self
+        }
+    }
+}
+
+impl Default for ZObjectContext {
+    fn default() -> ZObjectContext {
+        ZObjectContext::new(false)
+    }
+}
 
 pub trait ZObjectReader {
-    fn read<T: Read + Seek>(&mut self, reader: &mut T) -> Result<(), IOError>;
+    fn read<T: Read + Seek>(&mut self, reader: &mut T, ctx: &ZObjectContext) -> Result<(), IOError>;
 }
 
 pub trait ZObjectWriter {
-    fn write<T: Seek + Write>(writer: &mut T) -> Result<(), IOError>;
+    fn write<T: Seek + Write>(&self, writer: &mut T, ctx: &ZObjectContext) -> Result<(), IOError>;
 }
 
 pub trait ZObjectData {
     fn get_all_string_keys(&self) -> Vec<&dyn StringKey>;
     fn get_hkeys(&self) -> Vec<&HKey>;
     fn get_skeys(&self) -> Vec<&SKey>;
-}
\ No newline at end of file
+}
+
+/// Value that can be read/written as a zobject field
+pub trait ZObjectField: Sized {
+    fn read_field<T: Read + Seek>(reader: &mut T, ctx: &ZObjectContext) -> Result<Self, IOError>;
+    fn write_field<T: Seek + Write>(&self, writer: &mut T, ctx: &ZObjectContext) -> Result<(), IOError>;
+
+    fn collect_string_keys<'a>(&'a self, _keys: &mut Vec<&'a dyn StringKey>) { }
+    fn collect_hkeys<'a>(&'a self, _keys: &mut Vec<&'a HKey>) { }
+    fn collect_skeys<'a>(&'a self, _keys: &mut Vec<&'a SKey>) { }
+}
+
+impl ZObjectField for u8 {
+    fn read_field<T: Read + Seek>(reader: &mut T, _ctx: &ZObjectContext) -> Result<Self, IOError> {
+        let mut b = [0u8; 1];
+        reader.read_exact(&mut b)?;
+
+        Ok(b[0])
+    }
+
+    fn write_field<T: Seek + Write>(&self, writer: &mut T, _ctx: &ZObjectContext) -> Result<(), IOError> {
+        writer.write_all(&[*self])
+    }
+}
+
+impl ZObjectField for u16 {
+    fn read_field<T: Read + Seek>(reader: &mut T, ctx: &ZObjectContext) -> Result<Self, IOError> {
+        let mut b = [0u8; 2];
+        reader.read_exact(&mut b)?;
+
+        Ok(match ctx.big_endian {
+            true => u16::from_be_bytes(b),
+            _ => u16::from_le_bytes(b)
+        })
+    }
+
+    fn write_field<T: Seek + Write>(&self, writer: &mut T, ctx: &ZObjectContext) -> Result<(), IOError> {
+        let b = match ctx.big_endian {
+            true => self.to_be_bytes(),
+            _ => self.to_le_bytes()
+        };
+
+        writer.write_all(&b)
+    }
+}
+
+impl ZObjectField for u32 {
+    fn read_field<T: Read + Seek>(reader: &mut T, ctx: &ZObjectContext) -> Result<Self, IOError> {
+        read_u32(reader, ctx.big_endian)
+    }
+
+    fn write_field<T: Seek + Write>(&self, writer: &mut T, ctx: &ZObjectContext) -> Result<(), IOError> {
+        write_u32(writer, *self, ctx.big_endian)
+    }
+}
+
+impl ZObjectField for f32 {
+    fn read_field<T: Read + Seek>(reader: &mut T, ctx: &ZObjectContext) -> Result<Self, IOError> {
+        read_u32(reader, ctx.big_endian).map(f32::from_bits)
+    }
+
+    fn write_field<T: Seek + Write>(&self, writer: &mut T, ctx: &ZObjectContext) -> Result<(), IOError> {
+        write_u32(writer, self.to_bits(), ctx.big_endian)
+    }
+}
+
+impl ZObjectField for HKey {
+    fn read_field<T: Read + Seek>(reader: &mut T, ctx: &ZObjectContext) -> Result<Self, IOError> {
+        read_u64(reader, ctx.big_endian).map(|value| HKey { value })
+    }
+
+    fn write_field<T: Seek + Write>(&self, writer: &mut T, ctx: &ZObjectContext) -> Result<(), IOError> {
+        write_u64(writer, self.get_key(), ctx.big_endian)
+    }
+
+    fn collect_string_keys<'a>(&'a self, keys: &mut Vec<&'a dyn StringKey>) {
+        keys.push(self);
+    }
+
+    fn collect_hkeys<'a>(&'a self, keys: &mut Vec<&'a HKey>) {
+        keys.push(self);
+    }
+}
+
+impl ZObjectField for SKey {
+    fn read_field<T: Read + Seek>(reader: &mut T, ctx: &ZObjectContext) -> Result<Self, IOError> {
+        read_u64(reader, ctx.big_endian).map(|value| SKey { value })
+    }
+
+    fn write_field<T: Seek + Write>(&self, writer: &mut T, ctx: &ZObjectContext) -> Result<(), IOError> {
+        write_u64(writer, self.get_key(), ctx.big_endian)
+    }
+
+    fn collect_string_keys<'a>(&'a self, keys: &mut Vec<&'a dyn StringKey>) {
+        keys.push(self);
+    }
+
+    fn collect_skeys<'a>(&'a self, keys: &mut Vec<&'a SKey>) {
+        keys.push(self);
+    }
+}
+
+impl<F: ZObjectField> ZObjectField for Vec<F> {
+    fn read_field<T: Read + Seek>(reader: &mut T, ctx: &ZObjectContext) -> Result<Self, IOError> {
+        let count = read_u32(reader, ctx.big_endian)?;
+        read_u32(reader, ctx.big_endian)?; // Always 4
+
+        (0..count)
+            .map(|_| F::read_field(reader, ctx))
+            .collect()
+    }
+
+    fn write_field<T: Seek + Write>(&self, writer: &mut T, ctx: &ZObjectContext) -> Result<(), IOError> {
+        write_u32(writer, self.len() as u32, ctx.big_endian)?;
+        write_u32(writer, 4, ctx.big_endian)?;
+
+        for item in self.iter() {
+            item.write_field(writer, ctx)?;
+        }
+
+        Ok(())
+    }
+
+    fn collect_string_keys<'a>(&'a self, keys: &mut Vec<&'a dyn StringKey>) {
+        for item in self.iter() {
+            item.collect_string_keys(keys);
+        }
+    }
+
+    fn collect_hkeys<'a>(&'a self, keys: &mut Vec<&'a HKey>) {
+        for item in self.iter() {
+            item.collect_hkeys(keys);
+        }
+    }
+
+    fn collect_skeys<'a>(&'a self, keys: &mut Vec<&'a SKey>) {
+        for item in self.iter() {
+            item.collect_skeys(keys);
+        }
+    }
+}
+
+// Allows derived structs to be nested as fields
+impl<Z: Default + ZObjectData + ZObjectReader + ZObjectWriter> ZObjectField for Z {
+    fn read_field<T: Read + Seek>(reader: &mut T, ctx: &ZObjectContext) -> Result<Self, IOError> {
+        let mut value = Z::default();
+        value.read(reader, ctx)?;
+
+        Ok(value)
+    }
+
+    fn write_field<T: Seek + Write>(&self, writer: &mut T, ctx: &ZObjectContext) -> Result<(), IOError> {
+        self.write(writer, ctx)
+    }
+
+    fn collect_string_keys<'a>(&'a self, keys: &mut Vec<&'a dyn StringKey>) {
+        keys.extend(self.get_all_string_keys());
+    }
+
+    fn collect_hkeys<'a>(&'a self, keys: &mut Vec<&'a HKey>) {
+        keys.extend(self.get_hkeys());
+    }
+
+    fn collect_skeys<'a>(&'a self, keys: &mut Vec<&'a SKey>) {
+        keys.extend(self.get_skeys());
+    }
+}
+
+/// Reads items prefixed by count of type `C` (used by `#[zobject(count = C)]`)
+pub fn read_vec_with_count<C, F, T>(reader: &mut T, ctx: &ZObjectContext) -> Result<Vec<F>, IOError>
+    where C : ZObjectField + TryInto<usize>, F : ZObjectField, T : Read + Seek {
+    let count = C::read_field(reader, ctx)?
+        .try_into()
+        .map_err(|_| IOError::new(ErrorKind::InvalidData, "Invalid item count"))?;
+
+    (0..count)
+        .map(|_| F::read_field(reader, ctx))
+        .collect()
+}
+
+/// Writes items prefixed by count of type `C` (used by `#[zobject(count = C)]`)
+pub fn write_vec_with_count<C, F, T>(items: &[F], writer: &mut T, ctx: &ZObjectContext) -> Result<(), IOError>
+    where C : ZObjectField + TryFrom<usize>, F : ZObjectField, T : Seek + Write {
+    let count = C::try_from(items.len())
+        .map_err(|_| IOError::new(ErrorKind::InvalidInput, "Too many items for count type"))?;
+
+    count.write_field(writer, ctx)?;
+
+    for item in items.iter() {
+        item.write_field(writer, ctx)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bfforever_derive::{ZObjectData, ZObjectReader, ZObjectWriter};
+    use std::io::Cursor;
+    use super::*;
+
+    #[derive(Debug, Default, PartialEq, ZObjectData, ZObjectReader, ZObjectWriter)]
+    struct Nested {
+        key: SKey,
+        value: u16,
+    }
+
+    #[derive(Debug, Default, PartialEq, ZObjectData, ZObjectReader, ZObjectWriter)]
+    struct Attributes {
+        first: u32,
+        #[zobject(skip = 3)]
+        second: u8,
+        #[zobject(big_endian)]
+        always_big: u32,
+        #[zobject(count = u8)]
+        short_list: Vec<u16>,
+        #[zobject(count = u32, skip = 2)]
+        nested: Vec<Nested>,
+        path: HKey,
+    }
+
+    fn create_attributes() -> Attributes {
+        Attributes {
+            first: 0x01020304,
+            second: 0x05,
+            always_big: 0x06070809,
+            short_list: vec![0x0A0B, 0x0C0D],
+            nested: vec![Nested { key: SKey { value: 0x11 }, value: 0x1213 }],
+            path: HKey { value: 0x21 },
+        }
+    }
+
+    fn write_attributes(value: &Attributes, big_endian: bool) -> Vec<u8> {
+        let mut stream = Cursor::new(Vec::new());
+        value.write(&mut stream, &ZObjectContext::new(big_endian)).unwrap();
+
+        stream.into_inner()
+    }
+
+    #[test]
+    fn write_applies_attributes() {
+        let data = write_attributes(&create_attributes(), false);
+
+        let expected = [
+            &[0x04, 0x03, 0x02, 0x01][..],                // first
+            &[0x00, 0x00, 0x00, 0x05],                    // skip + second
+            &[0x06, 0x07, 0x08, 0x09],                    // always_big
+            &[0x02, 0x0B, 0x0A, 0x0D, 0x0C],              // short_list (u8 count)
+            &[0x00, 0x00, 0x01, 0x00, 0x00, 0x00],        // skip + nested (u32 count)
+            &[0x11, 0, 0, 0, 0, 0, 0, 0, 0x13, 0x12],     // nested[0]
+            &[0x21, 0, 0, 0, 0, 0, 0, 0],                 // path
+        ].concat();
+
+        assert_eq!(expected, data);
+    }
+
+    #[test]
+    fn round_trip_both_endians() {
+        let value = create_attributes();
+
+        for big_endian in [false, true] {
+            let data = write_attributes(&value, big_endian);
+
+            // Big endian field ignores context
+            assert_eq!(&[0x06, 0x07, 0x08, 0x09], &data[8..12]);
+
+            let mut read_value = Attributes::default();
+            read_value.read(&mut Cursor::new(data), &ZObjectContext::new(big_endian)).unwrap();
+
+            assert_eq!(value, read_value);
+        }
+    }
+
+    #[test]
+    fn read_skips_padding() {
+        let mut data = write_attributes(&create_attributes(), false);
+        data[4..7].fill(0xFF);
+        data[17..19].fill(0xFF);
+
+        let mut read_value = Attributes::default();
+        read_value.read(&mut Cursor::new(data), &ZObjectContext::default()).unwrap();
+
+        assert_eq!(create_attributes(), read_value);
+    }
+
+    #[test]
+    fn write_rejects_count_overflow() {
+        let value = Attributes {
+            short_list: vec![0; 256],
+            ..Default::default()
+        };
+
+        let mut stream = Cursor::new(Vec::new());
+        let result = value.write(&mut stream, &ZObjectContext::default());
+
+        assert_eq!(ErrorKind::InvalidInput, result.unwrap_err().kind());
+    }
+
+    #[test]
+    fn data_collects_nested_keys() {
+        let value = create_attributes();
+
+        assert_eq!(vec![&SKey { value: 0x11 }], value.get_skeys());
+        assert_eq!(vec![&HKey { value: 0x21 }], value.get_hkeys());
+        assert_eq!(2, value.get_all_string_keys().len());
+    }
+
+    #[derive(Debug, Default, PartialEq, ZObjectReader, ZObjectWriter)]
+    struct Versioned {
+        first: u32,
+        #[zobject(since_version = 2, skip = 1)]
+        added: u16,
+        last: u8,
+    }
+
+    #[test]
+    fn since_version_applies_to_context_version() {
+        let value = Versioned { first: 0x01020304, added: 0x0506, last: 0x07 };
+
+        for (version, expected) in [
+            (1, &[0x04, 0x03, 0x02, 0x01, 0x07][..]),
+            (2, &[0x04, 0x03, 0x02, 0x01, 0x00, 0x06, 0x05, 0x07]),
+            (ZOBJECT_LATEST_VERSION, &[0x04, 0x03, 0x02, 0x01, 0x00, 0x06, 0x05, 0x07]),
+        ] {
+            let ctx = ZObjectContext::default().with_version(version);
+
+            let mut stream = Cursor::new(Vec::new());
+            value.write(&mut stream, &ctx).unwrap();
+            let data = stream.into_inner();
+            assert_eq!(expected, &data[..]);
+
+            let mut read_value = Versioned::default();
+            read_value.read(&mut Cursor::new(data), &ctx).unwrap();
+
+            let expected_added = if version >= 2 { value.added } else { 0 };
+            assert_eq!(Versioned { added: expected_added, ..value }, read_value);
+        }
+    }
+
+    #[test]
+    fn with_big_endian_keeps_version() {
+        let ctx = ZObjectContext::new(false).with_version(3).with_big_endian();
+
+        assert!(ctx.big_endian);
+        assert_eq!(3, ctx.version);
+    }
+}
@@ -0,0 +1,63 @@
+// CRC-64/XZ (ECMA-182 polynomial, reflected)
+const CRC64_POLY: u64 = 0xC96C5795D7870F42;
+const CRC64_TABLE: [u64; 256] = create_crc64_table();
+
+const fn create_crc64_table() -> [u64; 256] {
+    let mut table = [0u64; 256];
+    let mut i = 0;
+
+    while i < 256 {
+        let mut crc = i as u64;
+        let mut j = 0;
+
+        while j < 8 {
+            crc = match crc & 1 {
+                1 => (crc >> 1) ^ CRC64_POLY,
+                _ => crc >> 1,
+            };
+
+            j += 1;
+        }
+
+        table[i] = crc;
+        i += 1;
+    }
+
+    table
+}
+
+pub fn hash_bytes(data: &[u8]) -> u64 {
+    let crc = data
+        .iter()
+        .fold(!0u64, |crc, b| CRC64_TABLE[((crc ^ *b as u64) & 0xFF) as usize] ^ (crc >> 8));
+
+    !crc
+}
+
+/// Computes string key as CRC-64/XZ of utf-8 bytes
+pub fn hash_string(value: &str) -> u64 {
+    hash_bytes(value.as_bytes())
+}
+
+/// Computes path key from lowercase path (paths are case-insensitive)
+pub fn hash_path(value: &str) -> u64 {
+    hash_string(&value.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hash_bytes_matches_crc64_xz_check() {
+        assert_eq!(0x995DC9BBDF1939FA, hash_bytes(b"123456789"));
+        assert_eq!(0, hash_bytes(b""));
+    }
+
+    #[test]
+    fn hash_path_ignores_case() {
+        assert_eq!(hash_path("Songs/Example/Example.fused.rif"), hash_path("songs/example/example.fused.rif"));
+        assert_eq!(hash_string("songs/example/example.fused.rif"), hash_path("Songs/Example/Example.fused.rif"));
+        assert_ne!(hash_string("Song"), hash_string("song"));
+    }
+}
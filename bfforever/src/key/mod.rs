@@ -0,0 +1,146 @@
+mod hash;
+mod registry;
+
+pub use hash::*;
+pub use registry::*;
+use std::convert::Infallible;
+use std::fmt::{Debug, Display, Formatter, Result as FmtResult};
+use std::str::FromStr;
+
+pub trait StringKey {
+    fn get_key(&self) -> u64;
+    fn set_key(&mut self, key: u64);
+
+    fn get_string(&self) -> Option<String> {
+        lookup_string(self.get_key())
+    }
+}
+
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+pub struct SKey {
+    pub value: u64,
+}
+
+impl SKey {
+    pub fn new(value: &str) -> SKey {
+        let key = hash_string(value);
+        register_string(key, value);
+
+        SKey { value: key }
+    }
+}
+
+impl StringKey for SKey {
+    fn get_key(&self) -> u64 {
+        self.value
+    }
+
+    fn set_key(&mut self, key: u64) {
+        self.value = key;
+    }
+}
+
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+pub struct HKey {
+    pub value: u64,
+}
+
+impl HKey {
+    pub fn new(value: &str) -> HKey {
+        let key = hash_path(value);
+        register_string(key, value);
+
+        HKey { value: key }
+    }
+}
+
+impl StringKey for HKey {
+    fn get_key(&self) -> u64 {
+        self.value
+    }
+
+    fn set_key(&mut self, key: u64) {
+        self.value = key;
+    }
+}
+
+impl FromStr for SKey {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(SKey::new(s))
+    }
+}
+
+impl FromStr for HKey {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(HKey::new(s))
+    }
+}
+
+impl From<&str> for SKey {
+    fn from(value: &str) -> Self {
+        SKey::new(value)
+    }
+}
+
+impl From<&str> for HKey {
+    fn from(value: &str) -> Self {
+        HKey::new(value)
+    }
+}
+
+fn fmt_key(f: &mut Formatter<'_>, name: &str, key: u64) -> FmtResult {
+    match lookup_string(key) {
+        Some(s) => write!(f, "{name}({s:?})"),
+        _ => write!(f, "{name}(0x{key:016X})"),
+    }
+}
+
+impl Debug for SKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        fmt_key(f, "SKey", self.value)
+    }
+}
+
+impl Debug for HKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        fmt_key(f, "HKey", self.value)
+    }
+}
+
+impl Display for SKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match lookup_string(self.value) {
+            Some(s) => write!(f, "{s}"),
+            _ => write!(f, "0x{:016X}", self.value),
+        }
+    }
+}
+
+impl Display for HKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match lookup_string(self.value) {
+            Some(s) => write!(f, "{s}"),
+            _ => write!(f, "0x{:016X}", self.value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_keys_hash_and_register() {
+        let skey = SKey::new("PartGuitar");
+        let hkey = HKey::new("Songs/Example/Example.fused.rif");
+
+        assert_eq!(hash_string("PartGuitar"), skey.value);
+        assert_eq!(hash_path("songs/example/example.fused.rif"), hkey.value);
+        assert_eq!(Some("PartGuitar".to_string()), skey.get_string());
+        assert_eq!(HKey::new("songs/example/example.fused.rif"), hkey);
+    }
+}
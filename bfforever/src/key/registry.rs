@@ -0,0 +1,38 @@
+use std::collections::HashMap;
+use std::sync::{OnceLock, RwLock};
+
+static STRING_REGISTRY: OnceLock<RwLock<HashMap<u64, String>>> = OnceLock::new();
+
+fn get_registry() -> &'static RwLock<HashMap<u64, String>> {
+    STRING_REGISTRY.get_or_init(|| RwLock::new(HashMap::new()))
+}
+
+/// Registers string for key so it can be resolved later
+pub fn register_string<T: Into<String>>(key: u64, value: T) {
+    let mut registry = get_registry()
+        .write()
+        .unwrap_or_else(|e| e.into_inner());
+
+    registry
+        .entry(key)
+        .or_insert_with(|| value.into());
+}
+
+/// Resolves previously registered string for key
+pub fn lookup_string(key: u64) -> Option<String> {
+    let registry = get_registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner());
+
+    registry
+        .get(&key)
+        .map(|s| s.to_owned())
+}
+
+pub fn get_registered_string_count() -> usize {
+    let registry = get_registry()
+        .read()
+        .unwrap_or_else(|e| e.into_inner());
+
+    registry.len()
+}
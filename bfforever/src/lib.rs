@@ -2,11 +2,17 @@
 
 pub mod audio;
 pub(crate) mod io;
-pub(crate) mod riff;
+mod key;
+pub mod midi;
+pub mod package;
+pub mod riff;
+pub mod string_table;
 pub mod texture;
 pub mod zobject;
 
-#[derive(Default)]
+pub use key::*;
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
 pub enum Localization {
     #[default]
     English,  // enUS
@@ -17,37 +23,30 @@ pub enum Localization {
     French,   // frFR
 }
 
-pub trait StringKey {
-    fn get_key(&self) -> u64;
-    fn set_key(&mut self, key: u64);
-}
-
-#[derive(Default)]
-pub struct SKey {
-    pub value: u64,
-}
-
-impl StringKey for SKey {
-    fn get_key(&self) -> u64 {
-        self.value
+impl Localization {
+    pub const ALL: [Localization; 6] = [
+        Localization::English,
+        Localization::Japanese,
+        Localization::German,
+        Localization::Italian,
+        Localization::Spanish,
+        Localization::French,
+    ];
+
+    pub fn get_code(&self) -> &'static str {
+        match self {
+            Localization::English => "enUS",
+            Localization::Japanese => "jaJP",
+            Localization::German => "deDE",
+            Localization::Italian => "itIT",
+            Localization::Spanish => "esES",
+            Localization::French => "frFR",
+        }
     }
 
-    fn set_key(&mut self, key: u64) {
-        self.value = key;
+    pub fn from_code(code: &str) -> Option<Localization> {
+        Localization::ALL
+            .into_iter()
+            .find(|l| l.get_code().eq_ignore_ascii_case(code))
     }
 }
-
-#[derive(Default)]
-pub struct HKey {
-    pub value: u64,
-}
-
-impl StringKey for HKey {
-    fn get_key(&self) -> u64 {
-        self.value
-    }
-
-    fn set_key(&mut self, key: u64) {
-        self.value = key;
-    }
-}
\ No newline at end of file
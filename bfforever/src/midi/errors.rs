@@ -0,0 +1,48 @@
+use crate::zobject::TempoMapError;
+use thiserror::Error as ThisError;
+use std::io::Error as IOError;
+
+#[derive(Debug, ThisError)]
+pub enum MidiError {
+    #[error("Unable to parse midi: {message}")]
+    ParseError {
+        message: String
+    },
+    #[error("Only metrical (ticks per quarter note) midi timing is supported")]
+    UnsupportedTiming,
+    #[error("Time signature of {numerator}/2^{denominator_pow} is unsupported")]
+    UnsupportedTimeSignature {
+        numerator: u8,
+        denominator_pow: u8
+    },
+    #[error("Package is missing {name} object")]
+    MissingObject {
+        name: &'static str
+    },
+    #[error("Invalid tempo map: {error}")]
+    TempoMapError {
+        error: TempoMapError
+    },
+    #[error("IO Error: {io_error}")]
+    IOError {
+        io_error: IOError
+    },
+}
+
+impl From<IOError> for MidiError {
+    fn from(err: IOError) -> Self {
+        MidiError::IOError { io_error: err }
+    }
+}
+
+impl From<TempoMapError> for MidiError {
+    fn from(err: TempoMapError) -> Self {
+        MidiError::TempoMapError { error: err }
+    }
+}
+
+impl From<midly::Error> for MidiError {
+    fn from(err: midly::Error) -> Self {
+        MidiError::ParseError { message: err.to_string() }
+    }
+}
@@ -0,0 +1,331 @@
+use crate::{HKey, Localization, SKey, StringKey};
+use crate::package::Package;
+use crate::zobject::*;
+use log::warn;
+use midly::{Format, Header, MetaMessage, MidiMessage, Smf, Timing, TrackEvent, TrackEventKind};
+use midly::num::{u4, u7, u15, u24, u28};
+use std::path::Path;
+use super::*;
+
+const NOTE_VELOCITY: u8 = 100;
+
+// Owned event data, converted to midly events once all tracks are built
+enum ExportEvent {
+    TrackName(String),
+    Text(String),
+    Lyric(String),
+    Tempo(u32), // Microseconds per quarter note
+    TimeSignature(u8, u8),
+    NoteOn(u8, u8, u8), // Channel, pitch, velocity
+    NoteOff(u8, u8),
+}
+
+impl ExportEvent {
+    // Note offs come first so back-to-back notes don't overlap
+    fn get_order(&self) -> u8 {
+        match self {
+            ExportEvent::NoteOff(..) => 0,
+            ExportEvent::NoteOn(..) => 2,
+            _ => 1,
+        }
+    }
+
+    fn to_kind(&self) -> TrackEventKind<'_> {
+        match self {
+            ExportEvent::TrackName(name) => TrackEventKind::Meta(MetaMessage::TrackName(name.as_bytes())),
+            ExportEvent::Text(text) => TrackEventKind::Meta(MetaMessage::Text(text.as_bytes())),
+            ExportEvent::Lyric(text) => TrackEventKind::Meta(MetaMessage::Lyric(text.as_bytes())),
+            ExportEvent::Tempo(mpq) => TrackEventKind::Meta(MetaMessage::Tempo(u24::new(*mpq))),
+            ExportEvent::TimeSignature(num, den_pow) => TrackEventKind::Meta(MetaMessage::TimeSignature(*num, *den_pow, 24, 8)),
+            ExportEvent::NoteOn(channel, pitch, velocity) => TrackEventKind::Midi {
+                channel: u4::new(*channel),
+                message: MidiMessage::NoteOn { key: u7::new(*pitch), vel: u7::new(*velocity) },
+            },
+            ExportEvent::NoteOff(channel, pitch) => TrackEventKind::Midi {
+                channel: u4::new(*channel),
+                message: MidiMessage::NoteOff { key: u7::new(*pitch), vel: u7::new(0) },
+            },
+        }
+    }
+}
+
+impl MidiChart {
+    /// Gets chart from song, timing, instrument and tab/vox objects in package
+    pub fn from_package(package: &Package) -> Result<Self, MidiError> {
+        let song_object = package
+            .objects()
+            .find(|o| matches!(o.object, ZObject::Song(_)))
+            .ok_or(MidiError::MissingObject { name: "Song" })?;
+
+        let mut chart = MidiChart {
+            song_path: song_object.dir_path.get_string().unwrap_or_default(),
+            string_tables: package.get_string_tables(),
+            ..Default::default()
+        };
+
+        for obj in package.objects() {
+            match &obj.object {
+                ZObject::Song(song) if obj.full_path.eq(&song_object.full_path) => chart.song = song.clone(),
+                ZObject::Tempo(tempo) => chart.tempo = tempo.clone(),
+                ZObject::TimeSignature(ts) => chart.time_signature = ts.clone(),
+                ZObject::Measure(measure) => chart.measure = measure.clone(),
+                ZObject::Section(section) => chart.sections = section.clone(),
+                ZObject::Instrument(instrument) => chart.add_instrument_tracks(obj.full_path, instrument, package),
+                _ => {}
+            }
+        }
+
+        Ok(chart)
+    }
+
+    fn add_instrument_tracks(&mut self, path: HKey, instrument: &Instrument, package: &Package) {
+        let difficulty = MidiDifficulty::ALL
+            .into_iter()
+            .find(|d| instrument.difficulty.eq(&HKey::new(d.get_name())));
+
+        let tracks = instrument.tracks
+            .iter()
+            .filter_map(|p| package.get_object(p))
+            .map(|o| &o.object);
+
+        for track in tracks {
+            match (track, difficulty) {
+                (ZObject::Tab(tab), Some(difficulty)) if instrument.instrument_type.eq(&HKey::new(INSTRUMENT_TYPE_GUITAR)) => {
+                    self.guitar.push((difficulty, tab.clone()));
+                },
+                (ZObject::Tab(tab), Some(difficulty)) if instrument.instrument_type.eq(&HKey::new(INSTRUMENT_TYPE_BASS)) => {
+                    self.bass.push((difficulty, tab.clone()));
+                },
+                (ZObject::Vox(vox), _) => self.vox = vox.clone(),
+                _ => warn!("Unable to export track of {:?} instrument with {:?} difficulty", instrument.instrument_type, instrument.difficulty),
+            }
+        }
+
+        self.instruments.push((path, instrument.clone()));
+    }
+
+    pub fn save<T: AsRef<Path>>(&self, mid_path: T) -> Result<(), MidiError> {
+        let data = self.to_bytes()?;
+        std::fs::write(mid_path, data)?;
+
+        Ok(())
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, MidiError> {
+        let tempo_map = TempoMap::from_zobjects(&self.tempo, &self.time_signature, DEFAULT_RESOLUTION)?;
+        let end_tick = tempo_map.seconds_to_tick(self.song.song_length as f64);
+
+        let mut tracks = vec![self.get_tempo_track(&tempo_map)];
+
+        if !self.sections.events.is_empty() || end_tick > 0 {
+            tracks.push(self.get_events_track(&tempo_map, end_tick));
+        }
+
+        if !self.guitar.is_empty() {
+            tracks.push(get_tab_track(GUITAR_TRACK_NAME, &self.guitar, &tempo_map));
+        }
+
+        if !self.bass.is_empty() {
+            tracks.push(get_tab_track(BASS_TRACK_NAME, &self.bass, &tempo_map));
+        }
+
+        if !self.vox.events.is_empty() {
+            tracks.push(self.get_vocals_track(&tempo_map));
+        }
+
+        let mut smf = Smf::new(Header::new(Format::Parallel, Timing::Metrical(u15::new(DEFAULT_RESOLUTION as u16))));
+
+        smf.tracks = tracks
+            .iter_mut()
+            .map(|events| to_track_events(events))
+            .collect();
+
+        let mut data = Vec::new();
+        smf.write_std(&mut data)?;
+
+        Ok(data)
+    }
+
+    fn get_string(&self, key: &SKey) -> String {
+        self.string_tables
+            .get(key, Localization::English)
+            .map(|s| s.to_owned())
+            .or_else(|| key.get_string())
+            .unwrap_or_default()
+    }
+
+    fn get_tempo_track(&self, tempo_map: &TempoMap) -> Vec<(u64, ExportEvent)> {
+        let mut events = vec![(0, ExportEvent::TrackName(self.get_string(&self.song.title)))];
+
+        for tempo in tempo_map.get_tempos() {
+            let mpq = (60_000_000.0 / tempo.qbpm).round() as u32;
+            events.push((tempo.tick, ExportEvent::Tempo(mpq.min(0xFF_FF_FF))));
+        }
+
+        for ts in tempo_map.get_time_signatures() {
+            let den_pow = ts.denominator.max(1).ilog2() as u8;
+            events.push((ts.tick, ExportEvent::TimeSignature(ts.numerator.min(u8::MAX as u32) as u8, den_pow)));
+        }
+
+        events
+    }
+
+    fn get_events_track(&self, tempo_map: &TempoMap, end_tick: u64) -> Vec<(u64, ExportEvent)> {
+        let mut events = vec![(0, ExportEvent::TrackName(EVENTS_TRACK_NAME.to_owned()))];
+
+        for section in self.sections.events.iter() {
+            let tick = tempo_map.milliseconds_to_tick(section.start);
+            events.push((tick, ExportEvent::Text(format!("[section {}]", self.get_string(&section.name)))));
+        }
+
+        events.push((end_tick, ExportEvent::Text("[end]".to_owned())));
+        events
+    }
+
+    fn get_vocals_track(&self, tempo_map: &TempoMap) -> Vec<(u64, ExportEvent)> {
+        let mut events = vec![(0, ExportEvent::TrackName(VOCALS_TRACK_NAME.to_owned()))];
+
+        for vox in self.vox.events.iter() {
+            let start = tempo_map.milliseconds_to_tick(vox.start);
+            let end = tempo_map.milliseconds_to_tick(vox.end);
+            let pitch = vox.pitch.clamp(VOCALS_MIN_PITCH as u32, VOCALS_MAX_PITCH as u32) as u8;
+
+            let lyric = self.get_string(&vox.lyric);
+
+            if !lyric.is_empty() {
+                events.push((start, ExportEvent::Lyric(lyric)));
+            }
+
+            events.push((start, ExportEvent::NoteOn(0, pitch, NOTE_VELOCITY)));
+            events.push((end, ExportEvent::NoteOff(0, pitch)));
+        }
+
+        events
+    }
+}
+
+fn get_tab_track(name: &str, tabs: &[(MidiDifficulty, Tab)], tempo_map: &TempoMap) -> Vec<(u64, ExportEvent)> {
+    let mut events = vec![(0, ExportEvent::TrackName(name.to_owned()))];
+
+    for (difficulty, tab) in tabs.iter() {
+        for note in tab.events.iter() {
+            let start = tempo_map.milliseconds_to_tick(note.start);
+            let end = tempo_map.milliseconds_to_tick(note.end);
+
+            let pitch = difficulty.get_base_pitch() + note.string.min(STRING_COUNT as u32 - 1) as u8;
+            let velocity = (FRET_VELOCITY_OFFSET as u32 + note.fret).min(127) as u8;
+            let channel = get_channel_from_techniques(note.techniques);
+
+            events.push((start, ExportEvent::NoteOn(channel, pitch, velocity)));
+            events.push((end, ExportEvent::NoteOff(channel, pitch)));
+        }
+    }
+
+    events
+}
+
+fn to_track_events(events: &mut [(u64, ExportEvent)]) -> Vec<TrackEvent<'_>> {
+    events.sort_by_key(|(tick, ev)| (*tick, ev.get_order()));
+
+    let mut prev_tick = 0;
+    let mut track = events
+        .iter()
+        .map(|(tick, ev)| {
+            let delta = *tick - prev_tick;
+            prev_tick = *tick;
+
+            TrackEvent {
+                delta: u28::new(delta as u32),
+                kind: ev.to_kind(),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    track.push(TrackEvent {
+        delta: u28::new(0),
+        kind: TrackEventKind::Meta(MetaMessage::EndOfTrack),
+    });
+
+    track
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zobject::ZObjectChunk;
+
+    fn add_object(package: &mut Package, path: &str, object: ZObject) {
+        package.add_object(ZObjectChunk {
+            full_path: HKey::new(path),
+            dir_path: HKey::new("songs/test"),
+            object,
+        });
+    }
+
+    fn create_package() -> Package {
+        let mut package = Package::new(false);
+
+        add_object(&mut package, "songs/test/test.song", ZObject::Song(Song {
+            song_length: 4.0,
+            ..Default::default()
+        }));
+
+        add_object(&mut package, "songs/test/guitar_hard.instrument", ZObject::Instrument(Instrument {
+            instrument_type: HKey::new(INSTRUMENT_TYPE_GUITAR),
+            difficulty: HKey::new(MidiDifficulty::Hard.get_name()),
+            tracks: vec![HKey::new("songs/test/guitar_hard.tab")],
+            ..Default::default()
+        }));
+
+        add_object(&mut package, "songs/test/guitar_hard.tab", ZObject::Tab(Tab {
+            events: vec![TabEntry { start: 500.0, end: 1000.0, string: 300, fret: 5, ..Default::default() }]
+        }));
+
+        add_object(&mut package, "songs/test/vox.instrument", ZObject::Instrument(Instrument {
+            instrument_type: HKey::new(INSTRUMENT_TYPE_VOX),
+            tracks: vec![HKey::new("songs/test/vox.vox")],
+            ..Default::default()
+        }));
+
+        add_object(&mut package, "songs/test/vox.vox", ZObject::Vox(Vox {
+            events: vec![VoxEntry { start: 1000.0, end: 1500.0, pitch: 300, lyric: SKey::default() }]
+        }));
+
+        package
+    }
+
+    #[test]
+    fn from_package_links_instrument_tracks() {
+        let chart = MidiChart::from_package(&create_package()).unwrap();
+
+        assert_eq!("songs/test", chart.song_path);
+        assert_eq!(4.0, chart.song.song_length);
+        assert_eq!(2, chart.instruments.len());
+
+        assert_eq!(1, chart.guitar.len());
+        assert_eq!(MidiDifficulty::Hard, chart.guitar[0].0);
+        assert!(chart.bass.is_empty());
+        assert_eq!(1, chart.vox.events.len());
+    }
+
+    #[test]
+    fn from_package_requires_song() {
+        let result = MidiChart::from_package(&Package::new(false));
+        assert!(matches!(result, Err(MidiError::MissingObject { name: "Song" })));
+    }
+
+    #[test]
+    fn to_bytes_clamps_out_of_range_values() {
+        let chart = MidiChart::from_package(&create_package()).unwrap();
+        let data = chart.to_bytes().unwrap();
+
+        let imported = MidiChart::from_bytes(&data, &MidiImportOptions::default()).unwrap();
+
+        assert_eq!(VOCALS_MAX_PITCH as u32, imported.vox.events[0].pitch);
+
+        let (difficulty, tab) = &imported.guitar[0];
+        assert_eq!(MidiDifficulty::Hard, *difficulty);
+        assert_eq!(STRING_COUNT as u32 - 1, tab.events[0].string);
+        assert_eq!(5, tab.events[0].fret);
+    }
+}
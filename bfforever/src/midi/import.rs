@@ -0,0 +1,370 @@
+use crate::{Localization, SKey};
+use crate::zobject::*;
+use midly::{MetaMessage, MidiMessage, Smf, Timing, TrackEvent, TrackEventKind};
+use std::collections::HashMap;
+use std::path::Path;
+use super::*;
+
+struct MidiNote {
+    start: u64, // Ticks
+    end: u64,
+    channel: u8,
+    pitch: u8,
+    velocity: u8,
+}
+
+impl MidiChart {
+    pub fn from_file<T: AsRef<Path>>(mid_path: T, options: &MidiImportOptions) -> Result<Self, MidiError> {
+        let data = std::fs::read(mid_path)?;
+        Self::from_bytes(&data, options)
+    }
+
+    pub fn from_bytes(data: &[u8], options: &MidiImportOptions) -> Result<Self, MidiError> {
+        let smf = Smf::parse(data)?;
+
+        let resolution = match smf.header.timing {
+            Timing::Metrical(tpq) if tpq.as_int() > 0 => tpq.as_int() as u32,
+            _ => return Err(MidiError::UnsupportedTiming),
+        };
+
+        let tracks = smf.tracks
+            .iter()
+            .map(|t| get_absolute_events(t))
+            .collect::<Vec<_>>();
+
+        // Tempo changes can be in any track (format 0 has single track)
+        let mut tempo_map = TempoMap::new(resolution);
+
+        for (tick, kind) in tracks.iter().flatten() {
+            match kind {
+                TrackEventKind::Meta(MetaMessage::Tempo(mpq)) => {
+                    tempo_map.add_tempo(*tick, 60_000_000.0 / (mpq.as_int().max(1) as f64))?;
+                },
+                TrackEventKind::Meta(MetaMessage::TimeSignature(num, den_pow, _, _)) => {
+                    // Beat must be at least 1 tick long
+                    let denominator = 1u32
+                        .checked_shl(*den_pow as u32)
+                        .filter(|d| ((resolution as u64) * 4) / (*d as u64) > 0)
+                        .filter(|_| *num > 0)
+                        .ok_or(MidiError::UnsupportedTimeSignature { numerator: *num, denominator_pow: *den_pow })?;
+
+                    tempo_map.add_time_signature(*tick, *num as u32, denominator)?;
+                },
+                _ => {}
+            }
+        }
+
+        let find_track = |name: &str| tracks
+            .iter()
+            .find(|t| get_track_name(t).is_some_and(|n| n.eq_ignore_ascii_case(name)));
+
+        let events_track = find_track(&options.events_track);
+
+        // Use [end] event if found, otherwise last event
+        let end_tick = events_track
+            .and_then(|t| t
+                .iter()
+                .find(|(_, kind)| get_text(kind).is_some_and(|text| text.eq("[end]")))
+                .map(|(tick, _)| *tick))
+            .unwrap_or_else(|| tracks
+                .iter()
+                .flat_map(|t| t.last().map(|(tick, _)| *tick))
+                .max()
+                .unwrap_or_default());
+
+        let end_seconds = tempo_map.tick_to_seconds(end_tick);
+
+        let mut chart = MidiChart {
+            song_path: options.song_path.to_owned(),
+            tempo: tempo_map.to_tempo(end_seconds),
+            time_signature: tempo_map.to_time_signature(end_seconds),
+            measure: tempo_map.to_measures(end_seconds),
+            ..Default::default()
+        };
+
+        let strings = chart.string_tables.get_table_mut(Localization::English);
+
+        // Empty options are left unset
+        let mut add_string = |value: &str| match value.is_empty() {
+            true => SKey::default(),
+            _ => strings.add_string(value),
+        };
+
+        chart.song = Song {
+            title: add_string(&options.title),
+            artist: add_string(&options.artist),
+            album: add_string(&options.album),
+            year: options.year,
+            song_length: end_seconds as f32,
+            ..Default::default()
+        };
+
+        if let Some(track) = events_track {
+            chart.sections = parse_sections(track, &tempo_map, end_tick, &mut chart.string_tables);
+        }
+
+        if let Some(track) = find_track(&options.guitar_track) {
+            chart.guitar = parse_tabs(track, &tempo_map);
+        }
+
+        if let Some(track) = find_track(&options.bass_track) {
+            chart.bass = parse_tabs(track, &tempo_map);
+        }
+
+        if let Some(track) = find_track(&options.vocals_track) {
+            chart.vox = parse_vox(track, &tempo_map, &mut chart.string_tables);
+        }
+
+        chart.update_instruments();
+        Ok(chart)
+    }
+}
+
+fn get_absolute_events<'a, 'b>(track: &'b [TrackEvent<'a>]) -> Vec<(u64, &'b TrackEventKind<'a>)> {
+    let mut tick = 0u64;
+
+    track
+        .iter()
+        .map(|ev| {
+            tick += ev.delta.as_int() as u64;
+            (tick, &ev.kind)
+        })
+        .collect()
+}
+
+fn get_track_name(track: &[(u64, &TrackEventKind)]) -> Option<String> {
+    track
+        .iter()
+        .find_map(|(_, kind)| match kind {
+            TrackEventKind::Meta(MetaMessage::TrackName(name)) => Some(String::from_utf8_lossy(name).into_owned()),
+            _ => None,
+        })
+}
+
+fn get_text(kind: &TrackEventKind) -> Option<String> {
+    match kind {
+        TrackEventKind::Meta(MetaMessage::Text(text))
+            | TrackEventKind::Meta(MetaMessage::Lyric(text))
+            | TrackEventKind::Meta(MetaMessage::Marker(text)) => Some(String::from_utf8_lossy(text).trim().to_owned()),
+        _ => None,
+    }
+}
+
+fn get_notes(track: &[(u64, &TrackEventKind)]) -> Vec<MidiNote> {
+    let mut notes = Vec::new();
+    let mut active: HashMap<(u8, u8), (u64, u8)> = HashMap::new();
+
+    for (tick, kind) in track.iter() {
+        let TrackEventKind::Midi { channel, message } = kind else {
+            continue;
+        };
+
+        let (pitch, velocity) = match message {
+            MidiMessage::NoteOn { key, vel } => (key.as_int(), vel.as_int()),
+            MidiMessage::NoteOff { key, .. } => (key.as_int(), 0),
+            _ => continue,
+        };
+
+        let channel = channel.as_int();
+
+        // Note on with 0 velocity is same as note off
+        if let Some((start, start_velocity)) = active.remove(&(channel, pitch)) {
+            notes.push(MidiNote {
+                start,
+                end: *tick,
+                channel,
+                pitch,
+                velocity: start_velocity,
+            });
+        }
+
+        if velocity > 0 {
+            active.insert((channel, pitch), (*tick, velocity));
+        }
+    }
+
+    notes.sort_by_key(|n| (n.start, n.pitch));
+    notes
+}
+
+fn parse_tabs(track: &[(u64, &TrackEventKind)], tempo_map: &TempoMap) -> Vec<(MidiDifficulty, Tab)> {
+    let notes = get_notes(track);
+
+    MidiDifficulty::ALL
+        .into_iter()
+        .map(|difficulty| {
+            let events = notes
+                .iter()
+                .filter_map(|note| match MidiDifficulty::from_pitch(note.pitch) {
+                    Some((d, string)) if d == difficulty => Some(TabEntry {
+                        start: tempo_map.tick_to_milliseconds(note.start),
+                        end: tempo_map.tick_to_milliseconds(note.end),
+                        string: string as u32,
+                        fret: note.velocity.saturating_sub(FRET_VELOCITY_OFFSET) as u32,
+                        finger: 0,
+                        techniques: get_techniques_from_channel(note.channel),
+                    }),
+                    _ => None,
+                })
+                .collect::<Vec<_>>();
+
+            (difficulty, Tab { events })
+        })
+        .filter(|(_, tab)| !tab.events.is_empty())
+        .collect()
+}
+
+fn parse_vox(track: &[(u64, &TrackEventKind)], tempo_map: &TempoMap, string_tables: &mut StringTables) -> Vox {
+    let lyrics = track
+        .iter()
+        .filter_map(|(tick, kind)| get_text(kind)
+            .filter(|text| !text.starts_with('['))
+            .map(|text| (*tick, text)))
+        .collect::<HashMap<_, _>>();
+
+    let strings = string_tables.get_table_mut(Localization::English);
+    let mut events: Vec<VoxEntry> = Vec::new();
+
+    for note in get_notes(track).iter().filter(|n| n.pitch >= VOCALS_MIN_PITCH && n.pitch <= VOCALS_MAX_PITCH) {
+        let start = tempo_map.tick_to_milliseconds(note.start);
+        let end = tempo_map.tick_to_milliseconds(note.end);
+
+        let lyric = lyrics
+            .get(&note.start)
+            .map(|l| l.trim_end_matches(['#', '^']))
+            .unwrap_or_default();
+
+        // Pitch slide continues previous syllable
+        if lyric.eq("+") {
+            if let Some(prev) = events.last_mut() {
+                prev.end = end;
+            }
+
+            continue;
+        }
+
+        events.push(VoxEntry {
+            start,
+            end,
+            pitch: note.pitch as u32,
+            lyric: match lyric.is_empty() {
+                true => SKey::default(),
+                _ => strings.add_string(lyric),
+            },
+        });
+    }
+
+    Vox { events }
+}
+
+fn parse_sections(track: &[(u64, &TrackEventKind)], tempo_map: &TempoMap, end_tick: u64, string_tables: &mut StringTables) -> Section {
+    let sections = track
+        .iter()
+        .filter_map(|(tick, kind)| {
+            let text = get_text(kind)?;
+            let name = text
+                .strip_prefix("[section ")
+                .or_else(|| text.strip_prefix("[prc_"))?
+                .strip_suffix(']')?
+                .trim()
+                .to_owned();
+
+            Some((*tick, name))
+        })
+        .collect::<Vec<_>>();
+
+    let strings = string_tables.get_table_mut(Localization::English);
+
+    let events = sections
+        .iter()
+        .enumerate()
+        .map(|(i, (tick, name))| SectionEntry {
+            start: tempo_map.tick_to_milliseconds(*tick),
+            end: tempo_map.tick_to_milliseconds(sections.get(i + 1).map(|(t, _)| *t).unwrap_or(end_tick)),
+            name: strings.add_string(name),
+        })
+        .collect();
+
+    Section { events }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HKey;
+    use midly::{Format, Header};
+    use midly::num::{u4, u7, u15, u28};
+
+    fn create_event(delta: u32, kind: TrackEventKind) -> TrackEvent {
+        TrackEvent { delta: u28::new(delta), kind }
+    }
+
+    fn create_note(delta: u32, pitch: u8, velocity: u8) -> TrackEvent<'static> {
+        create_event(delta, TrackEventKind::Midi {
+            channel: u4::new(0),
+            message: MidiMessage::NoteOn { key: u7::new(pitch), vel: u7::new(velocity) },
+        })
+    }
+
+    fn write_midi(tracks: Vec<Vec<TrackEvent>>) -> Vec<u8> {
+        let mut smf = Smf::new(Header::new(Format::Parallel, Timing::Metrical(u15::new(480))));
+
+        for mut track in tracks {
+            track.push(create_event(0, TrackEventKind::Meta(MetaMessage::EndOfTrack)));
+            smf.tracks.push(track);
+        }
+
+        let mut data = Vec::new();
+        smf.write_std(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn from_bytes_rejects_tiny_time_signature_denominator() {
+        let data = write_midi(vec![vec![
+            create_event(0, TrackEventKind::Meta(MetaMessage::TimeSignature(4, 12, 24, 8))),
+        ]]);
+
+        let result = MidiChart::from_bytes(&data, &MidiImportOptions::default());
+        assert!(matches!(result, Err(MidiError::UnsupportedTimeSignature { numerator: 4, denominator_pow: 12 })));
+    }
+
+    #[test]
+    fn from_bytes_creates_instruments() {
+        let data = write_midi(vec![
+            vec![
+                create_event(0, TrackEventKind::Meta(MetaMessage::TrackName(GUITAR_TRACK_NAME.as_bytes()))),
+                create_note(0, MidiDifficulty::Expert.get_base_pitch(), FRET_VELOCITY_OFFSET + 3),
+                create_note(480, MidiDifficulty::Expert.get_base_pitch(), 0),
+            ],
+            vec![
+                create_event(0, TrackEventKind::Meta(MetaMessage::TrackName(VOCALS_TRACK_NAME.as_bytes()))),
+                create_event(0, TrackEventKind::Meta(MetaMessage::Lyric(b"Hey"))),
+                create_note(0, 60, 100),
+                create_note(480, 60, 0),
+            ],
+        ]);
+
+        let options = MidiImportOptions {
+            title: "Title".to_owned(),
+            song_path: "songs/test".to_owned(),
+            ..Default::default()
+        };
+
+        let chart = MidiChart::from_bytes(&data, &options).unwrap();
+
+        assert_eq!(vec![HKey::new(INSTRUMENT_TYPE_GUITAR), HKey::new(INSTRUMENT_TYPE_VOX)], chart.song.instrument_tags);
+        assert_eq!(2, chart.instruments.len());
+
+        let (guitar_path, guitar) = &chart.instruments[0];
+        assert_eq!(&HKey::new("songs/test/guitar_expert.instrument"), guitar_path);
+        assert_eq!(HKey::new("expert"), guitar.difficulty);
+        assert_eq!(vec![HKey::new("songs/test/guitar_expert.tab")], guitar.tracks);
+        assert_eq!(vec![chart.get_vox_path()], chart.instruments[1].1.tracks);
+
+        // Empty options aren't added to string table
+        let strings = chart.string_tables.get_table(Localization::English).unwrap();
+        assert_eq!(SKey::default(), chart.song.artist);
+        assert_eq!(2, strings.len()); // Title + lyric
+    }
+}
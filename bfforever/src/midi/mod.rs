@@ -0,0 +1,206 @@
+mod errors;
+mod export;
+mod import;
+
+pub use errors::*;
+use crate::HKey;
+use crate::string_table::StringTables;
+use crate::zobject::*;
+
+// Default track names (Rock Band style)
+pub const GUITAR_TRACK_NAME: &str = "PART GUITAR";
+pub const BASS_TRACK_NAME: &str = "PART BASS";
+pub const VOCALS_TRACK_NAME: &str = "PART VOCALS";
+pub const EVENTS_TRACK_NAME: &str = "EVENTS";
+
+pub const STRING_COUNT: u8 = 6;
+pub const FRET_VELOCITY_OFFSET: u8 = 100; // Velocity = 100 + fret
+
+pub const VOCALS_MIN_PITCH: u8 = 36;
+pub const VOCALS_MAX_PITCH: u8 = 84;
+
+// Fretted note channels
+pub const CHANNEL_NORMAL: u8 = 0;
+pub const CHANNEL_BEND: u8 = 2;
+pub const CHANNEL_MUTED: u8 = 3;
+pub const CHANNEL_TAP: u8 = 4;
+pub const CHANNEL_HARMONIC: u8 = 5;
+
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub enum MidiDifficulty {
+    Easy,
+    Medium,
+    Hard,
+    #[default]
+    Expert,
+}
+
+impl MidiDifficulty {
+    pub const ALL: [MidiDifficulty; 4] = [
+        MidiDifficulty::Easy,
+        MidiDifficulty::Medium,
+        MidiDifficulty::Hard,
+        MidiDifficulty::Expert,
+    ];
+
+    /// Pitch of lowest string, each higher string is +1
+    pub fn get_base_pitch(&self) -> u8 {
+        match self {
+            MidiDifficulty::Easy => 24,
+            MidiDifficulty::Medium => 48,
+            MidiDifficulty::Hard => 72,
+            MidiDifficulty::Expert => 96,
+        }
+    }
+
+    /// Name used for instrument difficulty key
+    pub fn get_name(&self) -> &'static str {
+        match self {
+            MidiDifficulty::Easy => "easy",
+            MidiDifficulty::Medium => "medium",
+            MidiDifficulty::Hard => "hard",
+            MidiDifficulty::Expert => "expert",
+        }
+    }
+
+    pub fn from_pitch(pitch: u8) -> Option<(MidiDifficulty, u8)> {
+        MidiDifficulty::ALL
+            .into_iter()
+            .find(|d| pitch >= d.get_base_pitch() && pitch < d.get_base_pitch() + STRING_COUNT)
+            .map(|d| (d, pitch - d.get_base_pitch()))
+    }
+}
+
+#[derive(Debug)]
+pub struct MidiImportOptions {
+    pub title: String,
+    pub artist: String,
+    pub album: String,
+    pub year: u32,
+    pub song_path: String, // Directory of generated objects
+
+    pub guitar_track: String,
+    pub bass_track: String,
+    pub vocals_track: String,
+    pub events_track: String,
+}
+
+impl Default for MidiImportOptions {
+    fn default() -> MidiImportOptions {
+        MidiImportOptions {
+            title: String::default(),
+            artist: String::default(),
+            album: String::default(),
+            year: 0,
+            song_path: String::default(),
+
+            guitar_track: GUITAR_TRACK_NAME.to_owned(),
+            bass_track: BASS_TRACK_NAME.to_owned(),
+            vocals_track: VOCALS_TRACK_NAME.to_owned(),
+            events_track: EVENTS_TRACK_NAME.to_owned(),
+        }
+    }
+}
+
+/// Chart data converted from/to midi
+#[derive(Debug, Default)]
+pub struct MidiChart {
+    pub song_path: String,
+    pub song: Song,
+    pub tempo: Tempo,
+    pub time_signature: TimeSignature,
+    pub measure: Measure,
+    pub sections: Section,
+
+    pub guitar: Vec<(MidiDifficulty, Tab)>,
+    pub bass: Vec<(MidiDifficulty, Tab)>,
+    pub vox: Vox,
+    pub instruments: Vec<(HKey, Instrument)>, // Object path + instrument
+
+    pub string_tables: StringTables, // Song metadata, lyrics and section names
+}
+
+impl MidiChart {
+    /// Creates instruments linking tabs and vox, and updates song instrument tags
+    pub fn update_instruments(&mut self) {
+        let end = self.song.song_length * 1000.0;
+        let mut instruments = Vec::new();
+
+        for (instrument_type, tabs) in [(INSTRUMENT_TYPE_GUITAR, &self.guitar), (INSTRUMENT_TYPE_BASS, &self.bass)] {
+            for (difficulty, _) in tabs.iter() {
+                instruments.push((self.get_instrument_path(instrument_type, Some(*difficulty)), Instrument {
+                    instrument_type: HKey::new(instrument_type),
+                    difficulty: HKey::new(difficulty.get_name()),
+                    start: 0.0,
+                    end,
+                    tracks: vec![self.get_tab_path(instrument_type, *difficulty)],
+                    ..Default::default()
+                }));
+            }
+        }
+
+        if !self.vox.events.is_empty() {
+            // Vox isn't split by difficulty
+            instruments.push((self.get_instrument_path(INSTRUMENT_TYPE_VOX, None), Instrument {
+                instrument_type: HKey::new(INSTRUMENT_TYPE_VOX),
+                start: 0.0,
+                end,
+                tracks: vec![self.get_vox_path()],
+                ..Default::default()
+            }));
+        }
+
+        let mut tags: Vec<HKey> = Vec::new();
+
+        for (_, instrument) in instruments.iter() {
+            if !tags.contains(&instrument.instrument_type) {
+                tags.push(instrument.instrument_type);
+            }
+        }
+
+        self.song.instrument_tags = tags;
+        self.instruments = instruments;
+    }
+
+    pub fn get_tab_path(&self, instrument_type: &str, difficulty: MidiDifficulty) -> HKey {
+        self.get_object_path(&format!("{instrument_type}_{}.tab", difficulty.get_name()))
+    }
+
+    pub fn get_vox_path(&self) -> HKey {
+        self.get_object_path(&format!("{INSTRUMENT_TYPE_VOX}.vox"))
+    }
+
+    pub fn get_instrument_path(&self, instrument_type: &str, difficulty: Option<MidiDifficulty>) -> HKey {
+        match difficulty {
+            Some(difficulty) => self.get_object_path(&format!("{instrument_type}_{}.instrument", difficulty.get_name())),
+            _ => self.get_object_path(&format!("{instrument_type}.instrument")),
+        }
+    }
+
+    fn get_object_path(&self, file_name: &str) -> HKey {
+        match self.song_path.trim_end_matches('/') {
+            "" => HKey::new(file_name),
+            dir => HKey::new(&format!("{dir}/{file_name}")),
+        }
+    }
+}
+
+pub(crate) fn get_techniques_from_channel(channel: u8) -> u32 {
+    match channel {
+        CHANNEL_BEND => TECHNIQUE_BEND,
+        CHANNEL_MUTED => TECHNIQUE_MUTED,
+        CHANNEL_TAP => TECHNIQUE_TAP,
+        CHANNEL_HARMONIC => TECHNIQUE_HARMONIC,
+        _ => 0,
+    }
+}
+
+pub(crate) fn get_channel_from_techniques(techniques: u32) -> u8 {
+    match techniques {
+        t if (t & TECHNIQUE_TAP) != 0 => CHANNEL_TAP,
+        t if (t & TECHNIQUE_HARMONIC) != 0 => CHANNEL_HARMONIC,
+        t if (t & TECHNIQUE_MUTED) != 0 => CHANNEL_MUTED,
+        t if (t & TECHNIQUE_BEND) != 0 => CHANNEL_BEND,
+        _ => CHANNEL_NORMAL,
+    }
+}
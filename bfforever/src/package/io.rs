@@ -0,0 +1,258 @@
+use crate::HKey;
+use crate::io::{create_new_file, ZObjectContext, ZOBJECT_LATEST_VERSION};
+use crate::riff::*;
+use crate::string_table::StringTable;
+use crate::zobject::{RawZObject, ZObject, ZObjectChunk};
+use log::warn;
+use std::fs::File;
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use super::{Package, PackageChunk};
+
+impl Package {
+    pub fn from_file<T: AsRef<Path>>(rif_path: T) -> Result<Self, ReadRiffError> {
+        let mut file = File::open(rif_path)?;
+        Self::from_stream(&mut file)
+    }
+
+    pub fn from_stream<T: Read + Seek>(stream: &mut T) -> Result<Self, ReadRiffError> {
+        Self::from_stream_with_version(stream, ZOBJECT_LATEST_VERSION)
+    }
+
+    /// Reads package using zobject version for versioned fields
+    pub fn from_stream_with_version<T: Read + Seek>(stream: &mut T, version: u32) -> Result<Self, ReadRiffError> {
+        let mut riff = RiffReader::new_without_fourcc(stream)?;
+        let mut package = Package {
+            version,
+            ..Package::new(riff.is_big_endian())
+        };
+        let ctx = ZObjectContext::new(package.big_endian).with_version(version);
+
+        for chunk in riff.read_chunks()? {
+            match chunk {
+                Chunk::Index(_) => {
+                    // Re-computed on save
+                },
+                Chunk::StringTable(table) => {
+                    package.chunks.push(PackageChunk::StringTable(table));
+                },
+                Chunk::ZObject(ObjectChunk { header, data }) => {
+                    let object = read_zobject(header.type_key, data, &ctx)?;
+
+                    package.chunks.push(PackageChunk::ZObject(ZObjectChunk {
+                        full_path: header.full_path,
+                        dir_path: header.dir_path,
+                        object
+                    }));
+                },
+                Chunk::Unknown(chunk) => {
+                    package.chunks.push(PackageChunk::Unknown(chunk));
+                }
+            }
+        }
+
+        Ok(package)
+    }
+
+    pub fn save<T: AsRef<Path>>(&self, rif_path: T) -> Result<(), WriteRiffError> {
+        let mut file = create_new_file(rif_path)?;
+        self.write_to_stream(&mut file)
+    }
+
+    pub fn write_to_stream<T: Seek + Write>(&self, stream: &mut T) -> Result<(), WriteRiffError> {
+        let ctx = ZObjectContext::new(self.big_endian).with_version(self.version);
+
+        // Serialize chunk data first so index offsets can be computed
+        let mut chunks = Vec::new();
+
+        for chunk in self.chunks.iter() {
+            match chunk {
+                PackageChunk::StringTable(table) => {
+                    let mut data = Cursor::new(Vec::new());
+                    table.write(&mut data, self.big_endian)?;
+
+                    chunks.push((CHUNK_STRING_TABLE, table.full_path, StringTable::get_type_key(table.localization), data.into_inner()));
+                },
+                PackageChunk::ZObject(obj) => {
+                    let type_key = obj.object.get_type_key();
+
+                    let header = ZObjectHeader {
+                        full_path: obj.full_path,
+                        dir_path: obj.dir_path,
+                        type_key,
+                    };
+
+                    let mut data = Cursor::new(Vec::new());
+                    header.write(&mut data, self.big_endian)?;
+                    obj.object.write(&mut data, &ctx)?;
+
+                    chunks.push((CHUNK_ZOBJECT, obj.full_path, type_key, data.into_inner()));
+                },
+                PackageChunk::Unknown(chunk) => {
+                    let (full_path, type_key) = get_unknown_chunk_keys(chunk, self.big_endian);
+                    chunks.push((&chunk.id, full_path, type_key, chunk.data.clone()));
+                }
+            }
+        }
+
+        let mut index = IndexChunk {
+            entries: chunks
+                .iter()
+                .map(|(_, full_path, type_key, _)| IndexEntry {
+                    full_path: *full_path,
+                    type_key: *type_key,
+                    offset: 0,
+                })
+                .collect()
+        };
+
+        // Chunks are written after riff header + index
+        let mut offset = 8 + get_written_chunk_size(index.get_data_size() as u64);
+
+        for (entry, (_, _, _, data)) in index.entries.iter_mut().zip(chunks.iter()) {
+            entry.offset = offset as u32;
+            offset += get_written_chunk_size(data.len() as u64);
+        }
+
+        let mut index_data = Cursor::new(Vec::new());
+        index.write(&mut index_data, self.big_endian)?;
+
+        let mut riff = RiffWriter::new_without_fourcc(stream, self.big_endian)?;
+        riff.write_chunk(CHUNK_INDEX, index_data.get_ref())?;
+
+        for (id, _, _, data) in chunks.iter() {
+            riff.write_chunk(id, data)?;
+        }
+
+        riff.finish()?;
+        Ok(())
+    }
+}
+
+/// Gets index keys from chunk header (STbl/ZOBJ chunks start with zobject header)
+fn get_unknown_chunk_keys(chunk: &UnknownChunk, big_endian: bool) -> (HKey, HKey) {
+    match ZObjectHeader::parse(&mut Cursor::new(&chunk.data), big_endian) {
+        Ok(header) if chunk.data.len() >= 32 => (header.full_path, header.type_key),
+        _ => (HKey::default(), HKey::default()),
+    }
+}
+
+fn read_zobject(type_key: HKey, data: Vec<u8>, ctx: &ZObjectContext) -> Result<ZObject, ReadRiffError> {
+    let mut reader = Cursor::new(data);
+
+    match ZObject::read(type_key, &mut reader, ctx) {
+        Ok(object) => {
+            let read_size = reader.stream_position()?;
+            let data_size = reader.seek(SeekFrom::End(0))?;
+
+            if read_size == data_size || object.is_raw() {
+                return Ok(object);
+            }
+
+            warn!("Only read {read_size} of {data_size} bytes for {type_key:?} object, keeping as raw data");
+        },
+        Err(err) => {
+            warn!("Unable to read {type_key:?} object ({err}), keeping as raw data");
+        }
+    }
+
+    // Keep unparsed data so nothing is lost on save
+    Ok(ZObject::Raw(RawZObject {
+        type_key,
+        data: reader.into_inner()
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_truncated_object_keeps_raw_data() {
+        let header = ZObjectHeader {
+            full_path: HKey::new("songs/test/test.song"),
+            dir_path: HKey::new("songs/test"),
+            type_key: HKey::new("Song"),
+        };
+        let body = [0xAAu8; 16]; // Too short for song
+
+        let mut object_data = Cursor::new(Vec::new());
+        header.write(&mut object_data, false).unwrap();
+        object_data.write_all(&body).unwrap();
+
+        let mut riff = RiffWriter::new_without_fourcc(Cursor::new(Vec::new()), false).unwrap();
+        riff.write_chunk(CHUNK_ZOBJECT, object_data.get_ref()).unwrap();
+        let mut stream = riff.finish().unwrap();
+
+        stream.seek(SeekFrom::Start(0)).unwrap();
+        let package = Package::from_stream(&mut stream).unwrap();
+
+        let object = package.get_object(&header.full_path).unwrap();
+        let ZObject::Raw(raw) = &object.object else {
+            panic!("Expected raw object");
+        };
+
+        assert_eq!(header.type_key, raw.type_key);
+        assert_eq!(&body, raw.data.as_slice());
+    }
+
+    #[test]
+    fn round_trip_keeps_unknown_chunk_order() {
+        let header = ZObjectHeader {
+            full_path: HKey::new("songs/test/test.custom"),
+            dir_path: HKey::new("songs/test"),
+            type_key: HKey::new("StringTable@xxXX"), // Not a known localization
+        };
+
+        let mut unknown_data = Cursor::new(Vec::new());
+        header.write(&mut unknown_data, false).unwrap();
+        unknown_data.write_all(&[0x01, 0x02, 0x03]).unwrap();
+
+        let mut package = Package::new(false);
+        package.add_object(ZObjectChunk {
+            full_path: HKey::new("songs/test/test.song"),
+            dir_path: HKey::new("songs/test"),
+            object: ZObject::Raw(RawZObject { type_key: HKey::new("Custom1"), data: vec![0xAA; 4] }),
+        });
+        package.chunks.push(PackageChunk::Unknown(UnknownChunk { id: *CHUNK_STRING_TABLE, data: unknown_data.into_inner() }));
+        package.add_object(ZObjectChunk {
+            full_path: HKey::new("songs/test/test.other"),
+            dir_path: HKey::new("songs/test"),
+            object: ZObject::Raw(RawZObject { type_key: HKey::new("Custom2"), data: vec![0xBB; 4] }),
+        });
+
+        let mut stream = Cursor::new(Vec::new());
+        package.write_to_stream(&mut stream).unwrap();
+        let data = stream.into_inner();
+
+        // Index should include every chunk in order
+        let mut riff = RiffReader::new_without_fourcc(Cursor::new(data.clone())).unwrap();
+        let chunks = riff.read_chunks().unwrap();
+        let Chunk::Index(index) = &chunks[0] else {
+            panic!("Expected index chunk");
+        };
+
+        let expected_keys = [
+            (HKey::new("songs/test/test.song"), HKey::new("Custom1"), CHUNK_ZOBJECT),
+            (header.full_path, header.type_key, CHUNK_STRING_TABLE),
+            (HKey::new("songs/test/test.other"), HKey::new("Custom2"), CHUNK_ZOBJECT),
+        ];
+
+        assert_eq!(expected_keys.len(), index.entries.len());
+
+        for (entry, (full_path, type_key, id)) in index.entries.iter().zip(expected_keys.iter()) {
+            assert_eq!(*full_path, entry.full_path);
+            assert_eq!(*type_key, entry.type_key);
+
+            let offset = entry.offset as usize;
+            assert_eq!(&id[..], &data[offset..(offset + 4)]);
+        }
+
+        // Re-reading keeps same order
+        let read_package = Package::from_stream(&mut Cursor::new(data)).unwrap();
+        assert_eq!(3, read_package.chunks.len());
+        assert!(matches!(&read_package.chunks[0], PackageChunk::ZObject(o) if o.full_path.eq(&expected_keys[0].0)));
+        assert!(matches!(&read_package.chunks[1], PackageChunk::Unknown(c) if c.id.eq(CHUNK_STRING_TABLE)));
+        assert!(matches!(&read_package.chunks[2], PackageChunk::ZObject(o) if o.full_path.eq(&expected_keys[2].0)));
+    }
+}
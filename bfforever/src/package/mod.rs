@@ -0,0 +1,168 @@
+mod io;
+
+#[allow(unused_imports)] pub use io::*;
+use crate::{HKey, Localization};
+use crate::io::ZOBJECT_LATEST_VERSION;
+use crate::riff::UnknownChunk;
+use crate::string_table::{StringTable, StringTables};
+use crate::zobject::ZObjectChunk;
+
+/// Chunk stored in package (index is re-computed on save)
+#[derive(Debug)]
+pub enum PackageChunk {
+    StringTable(StringTable),
+    ZObject(ZObjectChunk),
+    Unknown(UnknownChunk),
+}
+
+/// Collection of zobjects and string tables stored in .rif file
+#[derive(Debug)]
+pub struct Package {
+    pub big_endian: bool,
+    pub version: u32, // Zobject version used for versioned fields
+    pub chunks: Vec<PackageChunk>, // Kept in file order
+}
+
+impl Default for Package {
+    fn default() -> Package {
+        Package {
+            big_endian: false,
+            version: ZOBJECT_LATEST_VERSION,
+            chunks: Vec::new(),
+        }
+    }
+}
+
+impl Package {
+    pub fn new(big_endian: bool) -> Package {
+        Package {
+            big_endian,
+            ..Default::default()
+        }
+    }
+
+    pub fn objects(&self) -> impl Iterator<Item = &ZObjectChunk> {
+        self.chunks
+            .iter()
+            .filter_map(|c| match c {
+                PackageChunk::ZObject(obj) => Some(obj),
+                _ => None,
+            })
+    }
+
+    pub fn objects_mut(&mut self) -> impl Iterator<Item = &mut ZObjectChunk> {
+        self.chunks
+            .iter_mut()
+            .filter_map(|c| match c {
+                PackageChunk::ZObject(obj) => Some(obj),
+                _ => None,
+            })
+    }
+
+    pub fn get_object(&self, full_path: &HKey) -> Option<&ZObjectChunk> {
+        self.objects()
+            .find(|o| o.full_path.eq(full_path))
+    }
+
+    pub fn get_object_mut(&mut self, full_path: &HKey) -> Option<&mut ZObjectChunk> {
+        self.objects_mut()
+            .find(|o| o.full_path.eq(full_path))
+    }
+
+    /// Adds object, replacing any existing object with same path
+    pub fn add_object(&mut self, object: ZObjectChunk) -> Option<ZObjectChunk> {
+        match self.find_object_index(&object.full_path) {
+            Some(i) => match std::mem::replace(&mut self.chunks[i], PackageChunk::ZObject(object)) {
+                PackageChunk::ZObject(existing) => Some(existing),
+                _ => None,
+            },
+            _ => {
+                self.chunks.push(PackageChunk::ZObject(object));
+                None
+            }
+        }
+    }
+
+    pub fn remove_object(&mut self, full_path: &HKey) -> Option<ZObjectChunk> {
+        match self.chunks.remove(self.find_object_index(full_path)?) {
+            PackageChunk::ZObject(obj) => Some(obj),
+            _ => None,
+        }
+    }
+
+    pub fn string_tables(&self) -> impl Iterator<Item = &StringTable> {
+        self.chunks
+            .iter()
+            .filter_map(|c| match c {
+                PackageChunk::StringTable(table) => Some(table),
+                _ => None,
+            })
+    }
+
+    /// Gets copy of string tables for all localizations
+    pub fn get_string_tables(&self) -> StringTables {
+        let mut tables = StringTables::new();
+
+        for table in self.string_tables() {
+            tables.add_table(table.clone());
+        }
+
+        tables
+    }
+
+    pub fn get_string_table(&self, localization: Localization) -> Option<&StringTable> {
+        self.string_tables()
+            .find(|t| t.localization == localization)
+    }
+
+    /// Gets table for localization, creating it if missing
+    pub fn get_string_table_mut(&mut self, localization: Localization) -> &mut StringTable {
+        let index = match self.find_string_table_index(localization) {
+            Some(i) => i,
+            _ => {
+                self.chunks.push(PackageChunk::StringTable(StringTable::new(localization)));
+                self.chunks.len() - 1
+            }
+        };
+
+        match &mut self.chunks[index] {
+            PackageChunk::StringTable(table) => table,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Adds table, replacing any existing table for localization
+    pub fn add_string_table(&mut self, table: StringTable) -> Option<StringTable> {
+        match self.find_string_table_index(table.localization) {
+            Some(i) => match std::mem::replace(&mut self.chunks[i], PackageChunk::StringTable(table)) {
+                PackageChunk::StringTable(existing) => Some(existing),
+                _ => None,
+            },
+            _ => {
+                self.chunks.push(PackageChunk::StringTable(table));
+                None
+            }
+        }
+    }
+
+    pub fn unknown_chunks(&self) -> impl Iterator<Item = &UnknownChunk> {
+        self.chunks
+            .iter()
+            .filter_map(|c| match c {
+                PackageChunk::Unknown(chunk) => Some(chunk),
+                _ => None,
+            })
+    }
+
+    fn find_object_index(&self, full_path: &HKey) -> Option<usize> {
+        self.chunks
+            .iter()
+            .position(|c| matches!(c, PackageChunk::ZObject(obj) if obj.full_path.eq(full_path)))
+    }
+
+    fn find_string_table_index(&self, localization: Localization) -> Option<usize> {
+        self.chunks
+            .iter()
+            .position(|c| matches!(c, PackageChunk::StringTable(table) if table.localization == localization))
+    }
+}
@@ -0,0 +1,268 @@
+use crate::{HKey, StringKey};
+use crate::io::{read_u32, read_u64, write_u32, write_u64};
+use crate::string_table::StringTable;
+use log::warn;
+use std::io::{Error as IOError, Read, Seek, SeekFrom, Write};
+use super::ReadRiffError;
+
+pub const CHUNK_INDEX: &[u8; 4] = b"INDX";
+pub const CHUNK_STRING_TABLE: &[u8; 4] = b"STbl";
+pub const CHUNK_ZOBJECT: &[u8; 4] = b"ZOBJ";
+
+#[derive(Debug)]
+pub enum Chunk {
+    Index(IndexChunk),
+    StringTable(StringTable),
+    ZObject(ObjectChunk),
+    Unknown(UnknownChunk),
+}
+
+#[derive(Debug, Default)]
+pub struct IndexChunk {
+    pub entries: Vec<IndexEntry>,
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct IndexEntry {
+    pub full_path: HKey,
+    pub type_key: HKey,
+    pub offset: u32, // Chunk offset in riff
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ZObjectHeader {
+    pub full_path: HKey,
+    pub dir_path: HKey,
+    pub type_key: HKey,
+}
+
+#[derive(Debug, Default)]
+pub struct ObjectChunk {
+    pub header: ZObjectHeader,
+    pub data: Vec<u8>,
+}
+
+#[derive(Debug)]
+pub struct UnknownChunk {
+    pub id: [u8; 4],
+    pub data: Vec<u8>,
+}
+
+impl Chunk {
+    pub fn get_id(&self) -> &[u8; 4] {
+        match self {
+            Chunk::Index(_) => CHUNK_INDEX,
+            Chunk::StringTable(_) => CHUNK_STRING_TABLE,
+            Chunk::ZObject(_) => CHUNK_ZOBJECT,
+            Chunk::Unknown(chunk) => &chunk.id,
+        }
+    }
+
+    pub(crate) fn parse<T: Read + Seek>(id: &[u8; 4], stream: &mut T, big_endian: bool) -> Result<Chunk, ReadRiffError> {
+        let start_pos = stream.stream_position()?;
+
+        Ok(match id {
+            CHUNK_INDEX => Chunk::Index(IndexChunk::parse(stream, big_endian)?),
+            CHUNK_STRING_TABLE => match StringTable::parse(stream, big_endian) {
+                Ok(table) => Chunk::StringTable(table),
+                Err(err) => {
+                    // Keep unrecognized string tables instead of failing whole riff
+                    warn!("Unable to parse string table ({err}), keeping as raw data");
+
+                    stream.seek(SeekFrom::Start(start_pos))?;
+                    Chunk::Unknown(UnknownChunk::parse(id, stream)?)
+                }
+            },
+            CHUNK_ZOBJECT => Chunk::ZObject(ObjectChunk::parse(stream, big_endian)?),
+            _ => Chunk::Unknown(UnknownChunk::parse(id, stream)?),
+        })
+    }
+}
+
+impl IndexChunk {
+    pub(crate) fn parse<T: Read + Seek>(stream: &mut T, big_endian: bool) -> Result<IndexChunk, ReadRiffError> {
+        let count = read_u32(stream, big_endian)?;
+        stream.seek(SeekFrom::Current(4))?; // Always 4
+
+        let mut entries = Vec::new();
+
+        for _ in 0..count {
+            let full_path = read_u64(stream, big_endian)?;
+            let type_key = read_u64(stream, big_endian)?;
+            let offset = read_u32(stream, big_endian)?;
+            stream.seek(SeekFrom::Current(4))?; // Always 0
+
+            entries.push(IndexEntry {
+                full_path: HKey { value: full_path },
+                type_key: HKey { value: type_key },
+                offset
+            });
+        }
+
+        Ok(IndexChunk {
+            entries
+        })
+    }
+
+    pub(crate) fn write<T: Seek + Write>(&self, stream: &mut T, big_endian: bool) -> Result<(), IOError> {
+        write_u32(stream, self.entries.len() as u32, big_endian)?;
+        write_u32(stream, 4, big_endian)?;
+
+        for entry in self.entries.iter() {
+            write_u64(stream, entry.full_path.get_key(), big_endian)?;
+            write_u64(stream, entry.type_key.get_key(), big_endian)?;
+            write_u32(stream, entry.offset, big_endian)?;
+            write_u32(stream, 0, big_endian)?;
+        }
+
+        Ok(())
+    }
+
+    /// Gets size of written chunk data
+    pub(crate) fn get_data_size(&self) -> usize {
+        8 + (self.entries.len() * 24)
+    }
+}
+
+impl ZObjectHeader {
+    pub(crate) fn parse<T: Read + Seek>(stream: &mut T, big_endian: bool) -> Result<ZObjectHeader, ReadRiffError> {
+        let full_path = read_u64(stream, big_endian)?;
+        let dir_path = read_u64(stream, big_endian)?;
+        let type_key = read_u64(stream, big_endian)?;
+        stream.seek(SeekFrom::Current(8))?; // Always 0
+
+        Ok(ZObjectHeader {
+            full_path: HKey { value: full_path },
+            dir_path: HKey { value: dir_path },
+            type_key: HKey { value: type_key },
+        })
+    }
+
+    pub(crate) fn write<T: Seek + Write>(&self, stream: &mut T, big_endian: bool) -> Result<(), IOError> {
+        write_u64(stream, self.full_path.get_key(), big_endian)?;
+        write_u64(stream, self.dir_path.get_key(), big_endian)?;
+        write_u64(stream, self.type_key.get_key(), big_endian)?;
+        write_u64(stream, 0, big_endian)?;
+
+        Ok(())
+    }
+}
+
+impl UnknownChunk {
+    pub(crate) fn parse<T: Read + Seek>(id: &[u8; 4], stream: &mut T) -> Result<UnknownChunk, ReadRiffError> {
+        let mut data = Vec::new();
+        stream.read_to_end(&mut data)?;
+
+        Ok(UnknownChunk {
+            id: id.to_owned(),
+            data
+        })
+    }
+}
+
+impl ObjectChunk {
+    pub(crate) fn parse<T: Read + Seek>(stream: &mut T, big_endian: bool) -> Result<ObjectChunk, ReadRiffError> {
+        let header = ZObjectHeader::parse(stream, big_endian)?;
+
+        // Object data is parsed later depending on type
+        let mut data = Vec::new();
+        stream.read_to_end(&mut data)?;
+
+        Ok(ObjectChunk {
+            header,
+            data
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn parse_unknown_string_table_keeps_data() {
+        let header = ZObjectHeader {
+            full_path: HKey { value: 1 },
+            dir_path: HKey { value: 2 },
+            type_key: HKey { value: 3 }, // Not a known localization
+        };
+
+        let mut stream = Cursor::new(Vec::new());
+        header.write(&mut stream, false).unwrap();
+        write_u64(&mut stream, 0, false).unwrap();
+
+        let data = stream.into_inner();
+        let chunk = Chunk::parse(CHUNK_STRING_TABLE, &mut Cursor::new(data.clone()), false).unwrap();
+
+        let Chunk::Unknown(chunk) = chunk else {
+            panic!("Expected unknown chunk");
+        };
+
+        assert_eq!(CHUNK_STRING_TABLE, &chunk.id);
+        assert_eq!(data, chunk.data);
+    }
+
+    #[test]
+    fn index_round_trip() {
+        let index = IndexChunk {
+            entries: vec![
+                IndexEntry { full_path: HKey { value: 0x0102030405060708 }, type_key: HKey { value: 0x11 }, offset: 0x20 },
+                IndexEntry { full_path: HKey { value: 0x21 }, type_key: HKey { value: 0x22 }, offset: 0x1234 },
+            ]
+        };
+
+        for big_endian in [false, true] {
+            let mut stream = Cursor::new(Vec::new());
+            index.write(&mut stream, big_endian).unwrap();
+
+            let data = stream.into_inner();
+            assert_eq!(index.get_data_size(), data.len());
+
+            let first_path = match big_endian {
+                true => 0x0102030405060708u64.to_be_bytes(),
+                _ => 0x0102030405060708u64.to_le_bytes(),
+            };
+            assert_eq!(&first_path, &data[8..16]);
+
+            let read_index = IndexChunk::parse(&mut Cursor::new(data), big_endian).unwrap();
+            assert_eq!(index.entries.len(), read_index.entries.len());
+
+            for (entry, read_entry) in index.entries.iter().zip(read_index.entries.iter()) {
+                assert_eq!(entry.full_path, read_entry.full_path);
+                assert_eq!(entry.type_key, read_entry.type_key);
+                assert_eq!(entry.offset, read_entry.offset);
+            }
+        }
+    }
+
+    #[test]
+    fn object_chunk_round_trip() {
+        let header = ZObjectHeader {
+            full_path: HKey { value: 0x0102030405060708 },
+            dir_path: HKey { value: 0x21 },
+            type_key: HKey { value: 0x31 },
+        };
+        let body = [0xAAu8, 0xBB, 0xCC];
+
+        for big_endian in [false, true] {
+            let mut stream = Cursor::new(Vec::new());
+            header.write(&mut stream, big_endian).unwrap();
+            stream.write_all(&body).unwrap();
+
+            let data = stream.into_inner();
+            assert_eq!(32 + body.len(), data.len());
+            assert_eq!(&[0u8; 8], &data[24..32]);
+
+            let chunk = Chunk::parse(CHUNK_ZOBJECT, &mut Cursor::new(data), big_endian).unwrap();
+            let Chunk::ZObject(chunk) = chunk else {
+                panic!("Expected zobject chunk");
+            };
+
+            assert_eq!(header.full_path, chunk.header.full_path);
+            assert_eq!(header.dir_path, chunk.header.dir_path);
+            assert_eq!(header.type_key, chunk.header.type_key);
+            assert_eq!(&body, chunk.data.as_slice());
+        }
+    }
+}
@@ -11,6 +11,19 @@ pub enum ReadRiffError {
     InvalidChunkIndex {
         index: usize
     },
+    #[error("Chunk {index} of size {size} extends past end of stream")]
+    TruncatedChunk {
+        index: usize,
+        size: u64
+    },
+    #[error("Unknown string table localization for type key 0x{type_key:016X}")]
+    UnknownLocalization {
+        type_key: u64
+    },
+    #[error("String offset {offset} is out of range")]
+    InvalidStringOffset {
+        offset: usize
+    },
     #[error("IO Error: {io_error}")]
     IOError {
         io_error: IOError
@@ -21,4 +34,22 @@ impl From<IOError> for ReadRiffError {
     fn from(err: IOError) -> Self {
         ReadRiffError::IOError { io_error: err }
     }
-}
\ No newline at end of file
+}
+
+#[derive(Debug, ThisError)]
+pub enum WriteRiffError {
+    #[error("Chunk size of {size} exceeds riff limit")]
+    ChunkTooLarge {
+        size: u64
+    },
+    #[error("IO Error: {io_error}")]
+    IOError {
+        io_error: IOError
+    },
+}
+
+impl From<IOError> for WriteRiffError {
+    fn from(err: IOError) -> Self {
+        WriteRiffError::IOError { io_error: err }
+    }
+}
@@ -4,10 +4,22 @@ use super::*;
 const MAGIC_RIFF: &[u8; 4] = b"RIFF";
 const MAGIC_RIFF_R: &[u8; 4] = b"FFIR"; // Big endian
 
+const CHUNK_ALIGNMENT: u64 = 2;
+const MAX_CHUNK_SIZE: u64 = u32::MAX as u64;
+
+fn get_padding_size(size: u64) -> u64 {
+    (CHUNK_ALIGNMENT - (size % CHUNK_ALIGNMENT)) % CHUNK_ALIGNMENT
+}
+
+/// Gets size of chunk once written (header + data + padding)
+pub(crate) fn get_written_chunk_size(data_size: u64) -> u64 {
+    8 + data_size + get_padding_size(data_size)
+}
+
 impl<T> RiffReader<T> where T : Reader {
     pub fn read_magic(&mut self) -> Result<(), ReadRiffError> {
         let mut buffer = [0u8; 4];
-        self.reader.read(&mut buffer)?;
+        self.reader.read_exact(&mut buffer)?;
 
         self.big_endian = match &buffer {
             MAGIC_RIFF => false,
@@ -18,10 +30,22 @@ impl<T> RiffReader<T> where T : Reader {
         Ok(())
     }
 
-    pub fn read_chunk_info(&mut self) -> Result<(), ReadRiffError> {
+    pub fn read_chunk_info(&mut self, read_fourcc: bool) -> Result<(), ReadRiffError> {
         let total_size = self.read_u32()? as u64;
         let end_pos = total_size + self.reader.stream_position()?;
 
+        if read_fourcc {
+            // Fourcc is included in riff size
+            let mut fourcc = self.read_bytes()?;
+
+            if self.big_endian {
+                // Reverse bytes
+                fourcc.reverse();
+            }
+
+            self.fourcc = Some(fourcc);
+        }
+
         loop {
             let chunk_pos = self.reader.stream_position()?;
 
@@ -37,8 +61,9 @@ impl<T> RiffReader<T> where T : Reader {
                 id_buf.reverse();
             }
 
-            // Skip chunk data
-            self.reader.seek(SeekFrom::Current(chunk_size as i64))?;
+            // Skip chunk data + padding
+            let skip_size = chunk_size + get_padding_size(chunk_size);
+            self.reader.seek(SeekFrom::Current(skip_size as i64))?;
 
             self.chunks.push(ChunkInfo {
                 id: id_buf,
@@ -52,7 +77,7 @@ impl<T> RiffReader<T> where T : Reader {
 
     pub fn read_u32(&mut self) -> Result<u32, ReadRiffError> {
         let mut buffer = [0u8; 4];
-        self.reader.read(&mut buffer)?;
+        self.reader.read_exact(&mut buffer)?;
 
         Ok(match self.big_endian {
             true => u32::from_be_bytes(buffer),
@@ -66,4 +91,78 @@ impl<T> RiffReader<T> where T : Reader {
 
         Ok(buffer)
     }
-}
\ No newline at end of file
+}
+
+impl<T> RiffWriter<T> where T : Writer {
+    pub fn write_magic(&mut self) -> Result<(), WriteRiffError> {
+        let magic = match self.big_endian {
+            true => MAGIC_RIFF_R,
+            _ => MAGIC_RIFF,
+        };
+
+        self.write_bytes(magic)
+    }
+
+    pub fn write_chunk(&mut self, id: &[u8; 4], data: &[u8]) -> Result<(), WriteRiffError> {
+        let chunk_pos = self.writer.stream_position()?;
+        let chunk_size = data.len() as u64;
+
+        if chunk_size > MAX_CHUNK_SIZE {
+            return Err(WriteRiffError::ChunkTooLarge { size: chunk_size });
+        }
+
+        let mut id_buf = id.to_owned();
+
+        if self.big_endian {
+            // Reverse bytes
+            id_buf.reverse();
+        }
+
+        self.write_bytes(&id_buf)?;
+        self.write_u32(chunk_size as u32)?;
+        self.write_bytes(data)?;
+
+        // Pad chunk data (not included in size)
+        let padding = [0u8; CHUNK_ALIGNMENT as usize];
+        self.write_bytes(&padding[..get_padding_size(chunk_size) as usize])?;
+
+        self.chunks.push(ChunkInfo {
+            id: id.to_owned(),
+            offset: chunk_pos,
+            size: chunk_size
+        });
+
+        Ok(())
+    }
+
+    pub(crate) fn write_riff_size(&mut self) -> Result<(), WriteRiffError> {
+        let end_pos = self.writer.stream_position()?;
+        let total_size = end_pos - (self.start_offset + 8);
+
+        if total_size > MAX_CHUNK_SIZE {
+            return Err(WriteRiffError::ChunkTooLarge { size: total_size });
+        }
+
+        // Update size after magic
+        self.writer.seek(SeekFrom::Start(self.start_offset + 4))?;
+        self.write_u32(total_size as u32)?;
+        self.writer.seek(SeekFrom::Start(end_pos))?;
+
+        Ok(())
+    }
+
+    pub fn write_u32(&mut self, value: u32) -> Result<(), WriteRiffError> {
+        let buffer = match self.big_endian {
+            true => value.to_be_bytes(),
+            _ => value.to_le_bytes()
+        };
+
+        self.write_bytes(&buffer)
+    }
+
+    pub fn write_bytes(&mut self, data: &[u8]) -> Result<(), WriteRiffError> {
+        self.writer.write_all(data)?;
+
+        Ok(())
+    }
+}
@@ -1,21 +1,28 @@
+mod chunk;
 mod errors;
 mod io;
 
-use std::io::{Cursor, Read, Seek, SeekFrom};
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
 
-#[allow(unused_imports)] use io::*;
+pub use chunk::*;
+#[allow(unused_imports)] pub(crate) use io::*;
 pub use errors::*;
 
 pub trait Reader : Read + Seek { }
+pub trait Writer : Seek + Write { }
 
-struct ChunkInfo {
+impl<T> Reader for T where T : Read + Seek { }
+impl<T> Writer for T where T : Seek + Write { }
+
+#[derive(Clone, Copy, Debug)]
+pub struct ChunkInfo {
     pub id: [u8; 4],
     pub offset: u64,
     pub size: u64,
 }
 
 impl ChunkInfo {
-    pub fn get_id_as_str<'a>(&'a self) -> Option<&'a str> {
+    pub fn get_id_as_str(&self) -> Option<&str> {
         std::str::from_utf8(&self.id).ok()
     }
 
@@ -62,26 +69,21 @@ impl<T> RiffReader<T> where T : Reader {
         };
 
         riff_reader.read_magic()?;
-
-        if read_fourcc {
-            let mut fourcc = riff_reader.read_bytes()?;
-
-            if riff_reader.big_endian {
-                // Reverse bytes
-                fourcc.reverse();
-            }
-
-            riff_reader.fourcc = Some(fourcc);
-        }
-
-        riff_reader.read_chunk_info()?;
+        riff_reader.read_chunk_info(read_fourcc)?;
         Ok(riff_reader)
     }
 
-    pub fn read_chunks(&mut self) -> Result<(), ReadRiffError> {
-        
-        
-        Ok(())
+    pub fn read_chunks(&mut self) -> Result<Vec<Chunk>, ReadRiffError> {
+        (0..self.chunks.len())
+            .map(|i| self.read_parsed_chunk(i))
+            .collect()
+    }
+
+    pub fn read_parsed_chunk(&mut self, index: usize) -> Result<Chunk, ReadRiffError> {
+        let mut data = self.read_chunk(index)?;
+        let id = self.chunks[index].id;
+
+        Chunk::parse(&id, &mut data, self.big_endian)
     }
 
     pub fn read_chunk(&mut self, index: usize) -> Result<Cursor<Vec<u8>>, ReadRiffError> {
@@ -94,26 +96,37 @@ impl<T> RiffReader<T> where T : Reader {
             (info.get_data_offset(), info.get_data_size())
         };
 
+        // Check size before allocating in case of corrupt header
+        let stream_size = self.reader.seek(SeekFrom::End(0))?;
+
+        if offset.saturating_add(size) > stream_size {
+            return Err(ReadRiffError::TruncatedChunk { index, size });
+        }
+
         self.reader.seek(SeekFrom::Start(offset))?;
 
         // Read data
         // TODO: Return new object with lazy read functionality?
         let mut buffer = vec![0u8; size as usize];
-        self.reader.read_exact(&mut buffer).unwrap();
+        self.reader.read_exact(&mut buffer)?;
 
         Ok(Cursor::new(buffer))
     }
 
+    pub fn is_big_endian(&self) -> bool {
+        self.big_endian
+    }
+
     pub fn has_fourcc(&self) -> bool {
         self.fourcc.is_some()
     }
 
-    pub fn get_fourcc<'a>(&'a self) -> Option<&'a [u8; 4]> {
+    pub fn get_fourcc(&self) -> Option<&[u8; 4]> {
         self.fourcc
             .as_ref()
     }
 
-    pub fn get_fourcc_as_str<'a>(&'a self) -> Option<&'a str> {
+    pub fn get_fourcc_as_str(&self) -> Option<&str> {
         self.fourcc
             .as_ref()
             .and_then(|fcc| std::str::from_utf8(fcc).ok())
@@ -122,4 +135,159 @@ impl<T> RiffReader<T> where T : Reader {
     pub fn get_chunk_count(&self) -> usize {
         self.chunks.len()
     }
-}
\ No newline at end of file
+
+    pub fn get_chunk_info(&self, index: usize) -> Option<&ChunkInfo> {
+        self.chunks.get(index)
+    }
+}
+
+pub struct RiffWriter<T> where T : Writer {
+    writer: T,
+    big_endian: bool,
+    fourcc: Option<[u8; 4]>,
+    start_offset: u64,
+    chunks: Vec<ChunkInfo>,
+}
+
+impl<T> RiffWriter<T> where T : Writer {
+    pub fn new(writer: T, fourcc: [u8; 4], big_endian: bool) -> Result<Self, WriteRiffError> {
+        RiffWriter::write(writer, Some(fourcc), big_endian)
+    }
+
+    pub fn new_without_fourcc(writer: T, big_endian: bool) -> Result<Self, WriteRiffError> {
+        // BF riffs lack fourcc
+        RiffWriter::write(writer, None, big_endian)
+    }
+
+    fn write(mut writer: T, fourcc: Option<[u8; 4]>, big_endian: bool) -> Result<Self, WriteRiffError> {
+        let start_offset = writer.stream_position()?;
+
+        let mut riff_writer = Self {
+            writer,
+            big_endian,
+            fourcc,
+            start_offset,
+            chunks: Vec::new()
+        };
+
+        riff_writer.write_magic()?;
+        riff_writer.write_u32(0)?; // Updated when finished
+
+        if let Some(mut fourcc) = fourcc {
+            if riff_writer.big_endian {
+                // Reverse bytes
+                fourcc.reverse();
+            }
+
+            riff_writer.write_bytes(&fourcc)?;
+        }
+
+        Ok(riff_writer)
+    }
+
+    /// Finalizes riff size and returns underlying writer
+    pub fn finish(mut self) -> Result<T, WriteRiffError> {
+        self.write_riff_size()?;
+        Ok(self.writer)
+    }
+
+    pub fn is_big_endian(&self) -> bool {
+        self.big_endian
+    }
+
+    pub fn has_fourcc(&self) -> bool {
+        self.fourcc.is_some()
+    }
+
+    pub fn get_chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+
+    pub fn get_chunk_info(&self, index: usize) -> Option<&ChunkInfo> {
+        self.chunks.get(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_riff(fourcc: Option<[u8; 4]>, big_endian: bool, chunks: &[(&[u8; 4], &[u8])]) -> Vec<u8> {
+        let cursor = Cursor::new(Vec::new());
+
+        let mut writer = match fourcc {
+            Some(fourcc) => RiffWriter::new(cursor, fourcc, big_endian),
+            _ => RiffWriter::new_without_fourcc(cursor, big_endian),
+        }.unwrap();
+
+        for (id, data) in chunks {
+            writer.write_chunk(id, data).unwrap();
+        }
+
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn assert_round_trip(fourcc: Option<[u8; 4]>, big_endian: bool) {
+        let chunks: [(&[u8; 4], &[u8]); 3] = [
+            (b"INDX", &[1, 2, 3, 4]),
+            (b"STbl", &[5, 6, 7]), // Odd size
+            (b"ZOBJ", &[]),
+        ];
+
+        let data = write_riff(fourcc, big_endian, &chunks);
+        assert_eq!(0, data.len() % 2);
+
+        let mut reader = match fourcc {
+            Some(_) => RiffReader::new(Cursor::new(data)),
+            _ => RiffReader::new_without_fourcc(Cursor::new(data)),
+        }.unwrap();
+
+        assert_eq!(big_endian, reader.is_big_endian());
+        assert_eq!(fourcc.as_ref(), reader.get_fourcc());
+        assert_eq!(chunks.len(), reader.get_chunk_count());
+
+        for (i, (id, chunk_data)) in chunks.iter().enumerate() {
+            let info = *reader.get_chunk_info(i).unwrap();
+            assert_eq!(*id, &info.id);
+            assert_eq!(chunk_data.len() as u64, info.get_data_size());
+
+            let read_data = reader.read_chunk(i).unwrap().into_inner();
+            assert_eq!(*chunk_data, read_data.as_slice());
+        }
+    }
+
+    #[test]
+    fn round_trip_little_endian() {
+        assert_round_trip(None, false);
+        assert_round_trip(Some(*b"WAVE"), false);
+    }
+
+    #[test]
+    fn round_trip_big_endian() {
+        assert_round_trip(None, true);
+        assert_round_trip(Some(*b"WAVE"), true);
+    }
+
+    #[test]
+    fn read_chunk_rejects_size_past_end() {
+        let mut data = write_riff(Some(*b"WAVE"), false, &[(b"data", &[1, 2, 3, 4])]);
+        data[16..20].copy_from_slice(&u32::MAX.to_le_bytes());
+        data[4..8].copy_from_slice(&u32::MAX.to_le_bytes());
+
+        let mut reader = RiffReader::new(Cursor::new(data)).unwrap();
+        assert_eq!(u32::MAX as u64, reader.get_chunk_info(0).unwrap().get_data_size());
+        assert!(matches!(reader.read_chunk(0), Err(ReadRiffError::TruncatedChunk { index: 0, .. })));
+    }
+
+    #[test]
+    fn write_big_endian_layout() {
+        let data = write_riff(Some(*b"WAVE"), true, &[(b"STbl", &[5, 6, 7])]);
+
+        assert_eq!(b"FFIR", &data[0..4]);
+        assert_eq!(4 + 8 + 4, u32::from_be_bytes(data[4..8].try_into().unwrap()));
+        assert_eq!(b"EVAW", &data[8..12]);
+        assert_eq!(b"lbTS", &data[12..16]);
+        assert_eq!(3, u32::from_be_bytes(data[16..20].try_into().unwrap()));
+        assert_eq!(&[5, 6, 7, 0], &data[20..24]); // Padded
+    }
+}
@@ -0,0 +1,158 @@
+use crate::{Localization, SKey, StringKey};
+use crate::io::{read_u32, read_u64, write_u32, write_u64};
+use crate::key::register_string;
+use crate::riff::{ReadRiffError, ZObjectHeader};
+use std::io::{Error as IOError, Read, Seek, SeekFrom, Write};
+use super::StringTable;
+
+impl StringTable {
+    pub(crate) fn parse<T: Read + Seek>(stream: &mut T, big_endian: bool) -> Result<StringTable, ReadRiffError> {
+        let header = ZObjectHeader::parse(stream, big_endian)?;
+
+        let localization = Localization::ALL
+            .into_iter()
+            .find(|l| StringTable::get_type_key(*l).eq(&header.type_key))
+            .ok_or(ReadRiffError::UnknownLocalization { type_key: header.type_key.get_key() })?;
+
+        let count = read_u32(stream, big_endian)?;
+        stream.seek(SeekFrom::Current(4))?; // Always 4
+
+        let mut offsets = Vec::new();
+
+        for _ in 0..count {
+            let key = read_u64(stream, big_endian)?;
+            let offset = read_u32(stream, big_endian)?;
+            stream.seek(SeekFrom::Current(4))?; // Always 0
+
+            offsets.push((key, offset as usize));
+        }
+
+        // Remaining data is null-terminated strings
+        let mut string_data = Vec::new();
+        stream.read_to_end(&mut string_data)?;
+
+        let mut table = StringTable {
+            full_path: header.full_path,
+            dir_path: header.dir_path,
+            ..StringTable::new(localization)
+        };
+
+        for (key, offset) in offsets {
+            let str_data = string_data
+                .get(offset..)
+                .ok_or(ReadRiffError::InvalidStringOffset { offset })?;
+
+            let str_length = str_data
+                .iter()
+                .position(|c| c.eq(&b'\0'))
+                .unwrap_or(str_data.len());
+
+            let value = String::from_utf8_lossy(&str_data[..str_length]).into_owned();
+            register_string(key, value.as_str());
+
+            table.insert(SKey { value: key }, value);
+        }
+
+        Ok(table)
+    }
+
+    pub(crate) fn write<T: Seek + Write>(&self, stream: &mut T, big_endian: bool) -> Result<(), IOError> {
+        let header = ZObjectHeader {
+            full_path: self.full_path,
+            dir_path: self.dir_path,
+            type_key: StringTable::get_type_key(self.localization),
+        };
+
+        header.write(stream, big_endian)?;
+
+        write_u32(stream, self.len() as u32, big_endian)?;
+        write_u32(stream, 4, big_endian)?;
+
+        let mut offset = 0;
+
+        for (key, value) in self.iter() {
+            write_u64(stream, key.get_key(), big_endian)?;
+            write_u32(stream, offset, big_endian)?;
+            write_u32(stream, 0, big_endian)?;
+
+            offset += value.len() as u32 + 1;
+        }
+
+        for (_, value) in self.iter() {
+            stream.write_all(value.as_bytes())?;
+            stream.write_all(&[0u8])?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::HKey;
+    use std::io::Cursor;
+
+    fn create_table() -> StringTable {
+        let mut table = StringTable {
+            full_path: HKey { value: 0x11 },
+            dir_path: HKey { value: 0x12 },
+            ..StringTable::new(Localization::Japanese)
+        };
+
+        table.insert(SKey { value: 0x21 }, "Intro");
+        table.insert(SKey { value: 0x22 }, "");
+        table.insert(SKey { value: 0x23 }, "Verse");
+        table
+    }
+
+    #[test]
+    fn write_string_offsets() {
+        let mut stream = Cursor::new(Vec::new());
+        create_table().write(&mut stream, false).unwrap();
+        let data = stream.into_inner();
+
+        // Header + count, entries, strings
+        let strings_start = 32 + 8 + (3 * 16);
+        assert_eq!(b"Intro\0\0Verse\0", &data[strings_start..]);
+
+        let offsets = (0..3)
+            .map(|i| {
+                let start = 40 + (i * 16) + 8;
+                u32::from_le_bytes(data[start..(start + 4)].try_into().unwrap())
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec![0, 6, 7], offsets);
+    }
+
+    #[test]
+    fn round_trip_both_endians() {
+        let table = create_table();
+
+        for big_endian in [false, true] {
+            let mut stream = Cursor::new(Vec::new());
+            table.write(&mut stream, big_endian).unwrap();
+
+            stream.set_position(0);
+            let read_table = StringTable::parse(&mut stream, big_endian).unwrap();
+
+            assert_eq!(table.full_path, read_table.full_path);
+            assert_eq!(table.dir_path, read_table.dir_path);
+            assert_eq!(table.localization, read_table.localization);
+            assert_eq!(table.iter().collect::<Vec<_>>(), read_table.iter().collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn parse_rejects_invalid_offset() {
+        let mut stream = Cursor::new(Vec::new());
+        create_table().write(&mut stream, false).unwrap();
+
+        let mut data = stream.into_inner();
+        data[48..52].copy_from_slice(&100u32.to_le_bytes());
+
+        let result = StringTable::parse(&mut Cursor::new(data), false);
+        assert!(matches!(result, Err(ReadRiffError::InvalidStringOffset { offset: 100 })));
+    }
+}
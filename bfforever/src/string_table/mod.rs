@@ -0,0 +1,194 @@
+mod io;
+
+#[allow(unused_imports)] pub use io::*;
+use crate::{HKey, Localization, SKey, StringKey};
+use std::collections::HashMap;
+
+#[derive(Clone, Debug, Default)]
+pub struct StringTable {
+    pub full_path: HKey,
+    pub dir_path: HKey,
+    pub localization: Localization,
+    entries: Vec<(SKey, String)>,
+    lookup: HashMap<u64, usize>,
+}
+
+impl StringTable {
+    pub fn new(localization: Localization) -> StringTable {
+        StringTable {
+            localization,
+            ..Default::default()
+        }
+    }
+
+    /// Type key used by string table chunks of given localization
+    pub fn get_type_key(localization: Localization) -> HKey {
+        HKey::new(&format!("StringTable@{}", localization.get_code()))
+    }
+
+    pub fn get(&self, key: &SKey) -> Option<&str> {
+        self.lookup
+            .get(&key.get_key())
+            .map(|i| self.entries[*i].1.as_str())
+    }
+
+    pub fn contains_key(&self, key: &SKey) -> bool {
+        self.lookup.contains_key(&key.get_key())
+    }
+
+    /// Adds or replaces string for key, returning previous value
+    pub fn insert<T: Into<String>>(&mut self, key: SKey, value: T) -> Option<String> {
+        let value = value.into();
+
+        match self.lookup.get(&key.get_key()) {
+            Some(i) => Some(std::mem::replace(&mut self.entries[*i].1, value)),
+            _ => {
+                self.lookup.insert(key.get_key(), self.entries.len());
+                self.entries.push((key, value));
+                None
+            }
+        }
+    }
+
+    /// Hashes string and adds it to table
+    pub fn add_string(&mut self, value: &str) -> SKey {
+        let key = SKey::new(value);
+        self.insert(key, value);
+        key
+    }
+
+    pub fn remove(&mut self, key: &SKey) -> Option<String> {
+        let index = self.lookup.remove(&key.get_key())?;
+        let (_, value) = self.entries.remove(index);
+
+        // Shift indices of following entries
+        for i in self.lookup.values_mut().filter(|i| **i > index) {
+            *i -= 1;
+        }
+
+        Some(value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&SKey, &str)> {
+        self.entries
+            .iter()
+            .map(|(k, v)| (k, v.as_str()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// Collection of string tables for each localization
+#[derive(Clone, Debug, Default)]
+pub struct StringTables {
+    tables: HashMap<Localization, StringTable>,
+}
+
+impl StringTables {
+    pub fn new() -> StringTables {
+        StringTables::default()
+    }
+
+    /// Gets string for localization, falling back to english if missing
+    pub fn get(&self, key: &SKey, localization: Localization) -> Option<&str> {
+        self.tables
+            .get(&localization)
+            .and_then(|t| t.get(key))
+            .or_else(|| self.tables
+                .get(&Localization::English)
+                .and_then(|t| t.get(key)))
+    }
+
+    pub fn insert<T: Into<String>>(&mut self, localization: Localization, key: SKey, value: T) -> Option<String> {
+        self.get_table_mut(localization)
+            .insert(key, value)
+    }
+
+    pub fn get_table(&self, localization: Localization) -> Option<&StringTable> {
+        self.tables.get(&localization)
+    }
+
+    /// Gets table for localization, creating it if missing
+    pub fn get_table_mut(&mut self, localization: Localization) -> &mut StringTable {
+        self.tables
+            .entry(localization)
+            .or_insert_with(|| StringTable::new(localization))
+    }
+
+    /// Adds table, returning any existing table for localization
+    pub fn add_table(&mut self, table: StringTable) -> Option<StringTable> {
+        self.tables.insert(table.localization, table)
+    }
+
+    pub fn remove_table(&mut self, localization: Localization) -> Option<StringTable> {
+        self.tables.remove(&localization)
+    }
+
+    pub fn tables(&self) -> impl Iterator<Item = &StringTable> {
+        // Keep consistent ordering
+        Localization::ALL
+            .iter()
+            .filter_map(|l| self.tables.get(l))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_replaces_existing_value() {
+        let mut table = StringTable::new(Localization::English);
+        let key = table.add_string("Intro");
+
+        assert_eq!(Some("Intro".to_string()), table.insert(key, "Verse"));
+        assert_eq!(Some("Verse"), table.get(&key));
+        assert_eq!(1, table.len());
+    }
+
+    #[test]
+    fn remove_shifts_following_entries() {
+        let mut table = StringTable::new(Localization::English);
+        let keys = ["First", "Second", "Third", "Fourth"].map(|s| table.add_string(s));
+
+        assert_eq!(Some("Second".to_string()), table.remove(&keys[1]));
+        assert_eq!(None, table.remove(&keys[1]));
+
+        assert_eq!(3, table.len());
+        assert!(!table.contains_key(&keys[1]));
+        assert_eq!(Some("First"), table.get(&keys[0]));
+        assert_eq!(Some("Third"), table.get(&keys[2]));
+        assert_eq!(Some("Fourth"), table.get(&keys[3]));
+
+        let values = table.iter().map(|(_, v)| v).collect::<Vec<_>>();
+        assert_eq!(vec!["First", "Third", "Fourth"], values);
+
+        // Lookup should still be valid after adding again
+        let key = table.add_string("Fifth");
+        assert_eq!(Some("Fifth"), table.get(&key));
+    }
+
+    #[test]
+    fn tables_get_falls_back_to_english() {
+        let mut tables = StringTables::new();
+        let key = SKey::new("Chorus");
+
+        assert_eq!(None, tables.get(&key, Localization::German));
+
+        tables.insert(Localization::English, key, "Chorus");
+        assert_eq!(Some("Chorus"), tables.get(&key, Localization::German));
+
+        tables.insert(Localization::German, key, "Refrain");
+        assert_eq!(Some("Refrain"), tables.get(&key, Localization::German));
+        assert_eq!(Some("Chorus"), tables.get(&key, Localization::English));
+
+        assert!(tables.remove_table(Localization::German).is_some());
+        assert_eq!(Some("Chorus"), tables.get(&key, Localization::German));
+    }
+}
@@ -9,20 +9,15 @@ unsafe impl Send for ValuesPtr {}
 unsafe impl Sync for ValuesPtr {}
 
 #[allow(non_camel_case_types)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Default)]
 #[repr(u32)]
 pub enum DXGI_Encoding {
     DXGI_FORMAT_BC1_UNORM =  8, // DXT1
+    #[default]
     DXGI_FORMAT_BC3_UNORM = 24, // DXT5
     DXGI_FORMAT_BC5_UNORM = 32, // ATI2
 }
 
-impl Default for DXGI_Encoding {
-    fn default() -> DXGI_Encoding {
-        DXGI_Encoding::DXGI_FORMAT_BC3_UNORM
-    }
-}
-
 impl From<u32> for DXGI_Encoding {
     fn from(num: u32) -> DXGI_Encoding {
         match num {
@@ -69,16 +64,14 @@ fn encode_dxt_with_lib(rgba: &[u8], dx_img: &mut [u8], width: u32, height: u32,
         // Needs to be this arrangement for image encoder library
         let rgb = rgba
             .chunks(4)
-            .map(|p| [p[0], p[1], p[2]])
-            .flatten()
+            .flat_map(|p| [p[0], p[1], p[2]])
             .collect::<Vec<_>>();
 
         dxt1_img = Some(rgb);
     }
 
     let image = dxt1_img
-        .as_ref()
-        .map(|dx| dx.as_slice())
+        .as_deref()
         .unwrap_or(rgba);
 
     // Encode dxt image
@@ -121,7 +121,7 @@ impl Texture2D {
             0x52 => TextureFormat::DXT1,
             0x54 => TextureFormat::DXT5,
             // 0x86 => TextureFormat::Raw,
-            f @ _ => {
+            f => {
                 return Err(Texture2DReadError::UnsupportedTextureFormat { format: f })
             }
         };
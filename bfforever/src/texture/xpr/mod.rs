@@ -61,11 +61,12 @@ impl Texture2D {
         let img_size = ((self.width * self.height) * bpp) / 8;
         let data = &untiled_img[..img_size as usize];
 
-        decode_dx_image(&data, &mut rgba, self.width, encoding, true);
+        decode_dx_image(data, &mut rgba, self.width, encoding, true);
         rgba
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn untile_texture(src: &[u8], tiled_width: u32, original_width: u32, tiled_height: u32, original_height: u32, block_size_x: u32, block_size_y: u32, bytes_per_block: u32) -> Vec<u8> {
     let dst_size = ((tiled_height * tiled_width) * ((bytes_per_block * 8) / (block_size_x * block_size_y))) / 8;
     let mut dst = vec![0u8; dst_size as usize];
@@ -112,12 +113,12 @@ fn get_tiled_offset(x: u32, y: u32, width: u32, log_bpb: u32) -> u32 {
     let offset = macro_part + ((micro_part & !0xF) << 1) + (micro_part & 0xF) + ((y & 1) << 4);
 
     // Mix bits again
-    return (((offset & !0x1FF) << 3) +              // Upper bits (offset bits [*-9])
+    (((offset & !0x1FF) << 3) +              // Upper bits (offset bits [*-9])
         ((y & 16) << 7) +                           // Next 1 bit
         ((offset & 0x1C0) << 2) +                   // Next 3 bits (offset bits [8-6])
         (((((y & 8) >> 2) + (x >> 3)) & 3) << 6) +  // Next 2 bits
         (offset & 0x3F)                             // Lower 6 bits (offset bits [5-0])
-        ) >> log_bpb;
+        ) >> log_bpb
 }
 
 fn align(ptr: u32, alignment: u32) -> u32 {
@@ -0,0 +1,236 @@
+use bfforever_derive::{ZObjectData, ZObjectReader, ZObjectWriter};
+use crate::{HKey, SKey};
+use super::Song;
+
+#[derive(Clone, Debug, Default, PartialEq, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct Catalog2Entry {
+    pub identifier: HKey, // Song identifier (entries are looked up by song_path)
+    pub song_type: u32,
+
+    pub title: SKey,
+    pub artist: SKey,
+    pub album: SKey,
+    pub description: SKey,
+    pub legend_tag: HKey,
+
+    pub song_length: f32,
+    pub guitar_intensity: f32,
+    pub bass_intensity: f32,
+    pub vox_intensity: f32,
+
+    pub era_tag: HKey,
+    pub year: u32,
+
+    pub song_path: HKey, // Full path of song zobject
+    pub texture_path: HKey,
+    pub preview_path: HKey,
+
+    pub metadata_tags: Vec<HKey>,
+    pub genre_tags: Vec<HKey>,
+    pub labels: Vec<SKey>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct Catalog2 {
+    pub entries: Vec<Catalog2Entry>,
+}
+
+impl Catalog2Entry {
+    /// Creates entry from song metadata
+    pub fn from_song(identifier: HKey, song_path: HKey, song: &Song) -> Catalog2Entry {
+        Catalog2Entry {
+            identifier,
+            song_type: 0,
+
+            title: song.title,
+            artist: song.artist,
+            album: song.album,
+            description: song.description,
+            legend_tag: song.legend_tag,
+
+            song_length: song.song_length,
+            guitar_intensity: song.guitar_intensity,
+            bass_intensity: song.bass_intensity,
+            vox_intensity: song.vox_intensity,
+
+            era_tag: song.era_tag,
+            year: song.year,
+
+            song_path,
+            texture_path: song.texture_path,
+            preview_path: song.preview_path,
+
+            metadata_tags: song.metadata_tags.clone(),
+            genre_tags: song.genre_tags.clone(),
+            labels: song.labels.clone(),
+        }
+    }
+}
+
+impl Catalog2 {
+    pub fn get_entry(&self, song_path: &HKey) -> Option<&Catalog2Entry> {
+        self.entries
+            .iter()
+            .find(|e| e.song_path.eq(song_path))
+    }
+
+    pub fn get_entry_mut(&mut self, song_path: &HKey) -> Option<&mut Catalog2Entry> {
+        self.entries
+            .iter_mut()
+            .find(|e| e.song_path.eq(song_path))
+    }
+
+    pub fn get_position(&self, song_path: &HKey) -> Option<usize> {
+        self.entries
+            .iter()
+            .position(|e| e.song_path.eq(song_path))
+    }
+
+    /// Adds entry to end of list, replacing existing entry for same song in place
+    pub fn add_entry(&mut self, entry: Catalog2Entry) -> Option<Catalog2Entry> {
+        match self.get_position(&entry.song_path) {
+            Some(i) => Some(std::mem::replace(&mut self.entries[i], entry)),
+            _ => {
+                self.entries.push(entry);
+                None
+            }
+        }
+    }
+
+    /// Inserts entry at index, removing any existing entry for same song
+    pub fn insert_entry(&mut self, index: usize, entry: Catalog2Entry) -> Option<Catalog2Entry> {
+        let existing = self.remove_entry(&entry.song_path);
+
+        let index = index.min(self.entries.len());
+        self.entries.insert(index, entry);
+
+        existing
+    }
+
+    /// Moves entry for song to new index
+    pub fn move_entry(&mut self, song_path: &HKey, index: usize) -> bool {
+        let Some(entry) = self.remove_entry(song_path) else {
+            return false;
+        };
+
+        let index = index.min(self.entries.len());
+        self.entries.insert(index, entry);
+
+        true
+    }
+
+    pub fn remove_entry(&mut self, song_path: &HKey) -> Option<Catalog2Entry> {
+        self.get_position(song_path)
+            .map(|i| self.entries.remove(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::{ZObjectContext, ZObjectReader, ZObjectWriter};
+    use std::io::Cursor;
+
+    fn create_entry(name: &str) -> Catalog2Entry {
+        let song = Song {
+            title: SKey::new(name),
+            genre_tags: vec![HKey::new("genre_rock")],
+            ..Default::default()
+        };
+
+        Catalog2Entry::from_song(HKey::new(name), HKey::new(&format!("songs/{name}/{name}.song")), &song)
+    }
+
+    fn get_titles(catalog: &Catalog2) -> Vec<SKey> {
+        catalog.entries
+            .iter()
+            .map(|e| e.title)
+            .collect()
+    }
+
+    fn create_catalog() -> Catalog2 {
+        let mut catalog = Catalog2::default();
+
+        for name in ["first", "second", "third"] {
+            catalog.add_entry(create_entry(name));
+        }
+
+        catalog
+    }
+
+    #[test]
+    fn from_song_keeps_identifier_and_path() {
+        let entry = create_entry("first");
+
+        assert_eq!(HKey::new("first"), entry.identifier);
+        assert_eq!(HKey::new("songs/first/first.song"), entry.song_path);
+        assert_eq!(SKey::new("first"), entry.title);
+        assert_eq!(vec![HKey::new("genre_rock")], entry.genre_tags);
+    }
+
+    #[test]
+    fn add_entry_replaces_in_place() {
+        let mut catalog = create_catalog();
+
+        let mut entry = create_entry("second");
+        entry.year = 1999;
+
+        assert_eq!(Some(create_entry("second")), catalog.add_entry(entry));
+        assert_eq!(3, catalog.entries.len());
+        assert_eq!(Some(1), catalog.get_position(&HKey::new("songs/second/second.song")));
+        assert_eq!(1999, catalog.entries[1].year);
+    }
+
+    #[test]
+    fn insert_entry_removes_duplicate() {
+        let mut catalog = create_catalog();
+
+        assert_eq!(Some(create_entry("third")), catalog.insert_entry(0, create_entry("third")));
+        assert_eq!(vec![SKey::new("third"), SKey::new("first"), SKey::new("second")], get_titles(&catalog));
+
+        assert!(catalog.insert_entry(100, create_entry("fourth")).is_none());
+        assert_eq!(SKey::new("fourth"), catalog.entries[3].title);
+    }
+
+    #[test]
+    fn move_entry_clamps_index() {
+        let mut catalog = create_catalog();
+
+        assert!(catalog.move_entry(&HKey::new("songs/first/first.song"), 100));
+        assert_eq!(vec![SKey::new("second"), SKey::new("third"), SKey::new("first")], get_titles(&catalog));
+
+        assert!(catalog.move_entry(&HKey::new("songs/first/first.song"), 1));
+        assert_eq!(vec![SKey::new("second"), SKey::new("first"), SKey::new("third")], get_titles(&catalog));
+
+        assert!(!catalog.move_entry(&HKey::new("songs/missing/missing.song"), 0));
+    }
+
+    #[test]
+    fn remove_entry() {
+        let mut catalog = create_catalog();
+        let path = HKey::new("songs/second/second.song");
+
+        assert_eq!(Some(create_entry("second")), catalog.remove_entry(&path));
+        assert!(catalog.remove_entry(&path).is_none());
+        assert!(catalog.get_entry(&path).is_none());
+        assert_eq!(vec![SKey::new("first"), SKey::new("third")], get_titles(&catalog));
+    }
+
+    #[test]
+    fn round_trip_both_endians() {
+        let catalog = create_catalog();
+
+        for big_endian in [false, true] {
+            let ctx = ZObjectContext::new(big_endian);
+
+            let mut stream = Cursor::new(Vec::new());
+            catalog.write(&mut stream, &ctx).unwrap();
+
+            stream.set_position(0);
+            let mut read_catalog = Catalog2::default();
+            read_catalog.read(&mut stream, &ctx).unwrap();
+
+            assert_eq!(catalog, read_catalog);
+        }
+    }
+}
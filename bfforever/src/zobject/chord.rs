@@ -0,0 +1,15 @@
+use bfforever_derive::{ZObjectData, ZObjectReader, ZObjectWriter};
+use super::{NamedEventEntry, NamedEvents};
+
+pub type ChordEntry = NamedEventEntry;
+
+#[derive(Clone, Debug, Default, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct Chord {
+    pub events: Vec<ChordEntry>,
+}
+
+impl NamedEvents for Chord {
+    fn get_events(&self) -> &[NamedEventEntry] {
+        &self.events
+    }
+}
@@ -0,0 +1,15 @@
+use thiserror::Error as ThisError;
+
+#[derive(Debug, ThisError)]
+pub enum TempoMapError {
+    #[error("Tempo of {qbpm} bpm is invalid")]
+    InvalidTempo {
+        qbpm: f64
+    },
+    #[error("Time signature of {numerator}/{denominator} is invalid for resolution of {resolution}")]
+    InvalidTimeSignature {
+        numerator: u32,
+        denominator: u32,
+        resolution: u32
+    },
+}
@@ -0,0 +1,15 @@
+use bfforever_derive::{ZObjectData, ZObjectReader, ZObjectWriter};
+use super::{NamedEventEntry, NamedEvents};
+
+pub type EventEntry = NamedEventEntry;
+
+#[derive(Clone, Debug, Default, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct Event {
+    pub events: Vec<EventEntry>,
+}
+
+impl NamedEvents for Event {
+    fn get_events(&self) -> &[NamedEventEntry] {
+        &self.events
+    }
+}
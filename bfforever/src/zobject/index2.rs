@@ -0,0 +1,167 @@
+use bfforever_derive::{ZObjectData, ZObjectReader, ZObjectWriter};
+use crate::HKey;
+
+#[derive(Clone, Debug, Default, PartialEq, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct Index2PackageEntry {
+    pub package: HKey,   // Package def path
+    pub file_path: HKey, // Package file (.rif) path
+    pub offset: u32,     // Offset of object chunk in package file
+}
+
+#[derive(Clone, Debug, Default, PartialEq, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct Index2Entry {
+    pub full_path: HKey,
+    pub type_key: HKey,
+    pub package_entries: Vec<Index2PackageEntry>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct Index2 {
+    pub entries: Vec<Index2Entry>,
+}
+
+impl Index2 {
+    pub fn get_entry(&self, full_path: &HKey) -> Option<&Index2Entry> {
+        self.entries
+            .iter()
+            .find(|e| e.full_path.eq(full_path))
+    }
+
+    pub fn get_entry_mut(&mut self, full_path: &HKey) -> Option<&mut Index2Entry> {
+        self.entries
+            .iter_mut()
+            .find(|e| e.full_path.eq(full_path))
+    }
+
+    /// Adds entry, replacing existing entry with same path
+    pub fn add_entry(&mut self, entry: Index2Entry) -> Option<Index2Entry> {
+        match self.entries.iter().position(|e| e.full_path.eq(&entry.full_path)) {
+            Some(i) => Some(std::mem::replace(&mut self.entries[i], entry)),
+            _ => {
+                self.entries.push(entry);
+                None
+            }
+        }
+    }
+
+    /// Adds object location, creating entry if missing
+    pub fn add_object(&mut self, full_path: HKey, type_key: HKey, package_entry: Index2PackageEntry) {
+        let entry = match self.entries.iter().position(|e| e.full_path.eq(&full_path)) {
+            Some(i) => &mut self.entries[i],
+            _ => {
+                self.entries.push(Index2Entry {
+                    full_path,
+                    type_key,
+                    package_entries: Vec::new(),
+                });
+
+                self.entries.last_mut().unwrap()
+            }
+        };
+
+        entry.type_key = type_key;
+        entry.package_entries.retain(|p| !p.package.eq(&package_entry.package));
+        entry.package_entries.push(package_entry);
+    }
+
+    pub fn remove_entry(&mut self, full_path: &HKey) -> Option<Index2Entry> {
+        self.entries
+            .iter()
+            .position(|e| e.full_path.eq(full_path))
+            .map(|i| self.entries.remove(i))
+    }
+
+    /// Removes all object locations referencing package
+    pub fn remove_package(&mut self, package: &HKey) {
+        for entry in self.entries.iter_mut() {
+            entry.package_entries.retain(|p| !p.package.eq(package));
+        }
+
+        self.entries.retain(|e| !e.package_entries.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::{ZObjectContext, ZObjectReader, ZObjectWriter};
+    use std::io::Cursor;
+
+    fn create_package_entry(package: &str, offset: u32) -> Index2PackageEntry {
+        Index2PackageEntry {
+            package: HKey::new(package),
+            file_path: HKey::new(&format!("{package}.rif")),
+            offset,
+        }
+    }
+
+    #[test]
+    fn add_object_replaces_package_entry() {
+        let mut index = Index2::default();
+        let path = HKey::new("songs/test/test.song");
+
+        index.add_object(path, HKey::new("Song"), create_package_entry("songs/test/test", 0x10));
+        index.add_object(path, HKey::new("Song"), create_package_entry("songs/test/update", 0x20));
+        index.add_object(path, HKey::new("Song2"), create_package_entry("songs/test/test", 0x30));
+
+        assert_eq!(1, index.entries.len());
+
+        let entry = index.get_entry(&path).unwrap();
+        assert_eq!(HKey::new("Song2"), entry.type_key);
+        assert_eq!(vec![create_package_entry("songs/test/update", 0x20), create_package_entry("songs/test/test", 0x30)], entry.package_entries);
+    }
+
+    #[test]
+    fn remove_package_drops_empty_entries() {
+        let mut index = Index2::default();
+        let shared_path = HKey::new("songs/test/test.song");
+        let single_path = HKey::new("songs/test/test.tab");
+
+        index.add_object(shared_path, HKey::new("Song"), create_package_entry("songs/test/test", 0x10));
+        index.add_object(shared_path, HKey::new("Song"), create_package_entry("songs/test/update", 0x20));
+        index.add_object(single_path, HKey::new("Tab"), create_package_entry("songs/test/test", 0x30));
+
+        index.remove_package(&HKey::new("songs/test/test"));
+
+        assert_eq!(1, index.entries.len());
+        assert!(index.get_entry(&single_path).is_none());
+        assert_eq!(vec![create_package_entry("songs/test/update", 0x20)], index.get_entry(&shared_path).unwrap().package_entries);
+    }
+
+    #[test]
+    fn add_and_remove_entry() {
+        let mut index = Index2::default();
+        let entry = Index2Entry { full_path: HKey::new("songs/test/test.song"), ..Default::default() };
+
+        assert!(index.add_entry(entry.clone()).is_none());
+        assert_eq!(Some(entry.clone()), index.add_entry(entry.clone()));
+        assert_eq!(1, index.entries.len());
+
+        assert_eq!(Some(entry.clone()), index.remove_entry(&entry.full_path));
+        assert!(index.remove_entry(&entry.full_path).is_none());
+    }
+
+    #[test]
+    fn round_trip_both_endians() {
+        let mut index = Index2::default();
+        index.add_object(HKey::new("songs/test/test.song"), HKey::new("Song"), create_package_entry("songs/test/test", 0x10));
+        index.add_object(HKey::new("songs/test/test.song"), HKey::new("Song"), create_package_entry("songs/test/update", 0x20));
+        index.add_object(HKey::new("songs/test/test.tab"), HKey::new("Tab"), create_package_entry("songs/test/test", 0x30));
+
+        for big_endian in [false, true] {
+            let ctx = ZObjectContext::new(big_endian);
+
+            let mut stream = Cursor::new(Vec::new());
+            index.write(&mut stream, &ctx).unwrap();
+
+            // Vec headers are count + 4, entries are path + type + package entries
+            assert_eq!(8 + (24 + (2 * 20)) + (24 + 20), stream.get_ref().len());
+
+            stream.set_position(0);
+            let mut read_index = Index2::default();
+            read_index.read(&mut stream, &ctx).unwrap();
+
+            assert_eq!(index, read_index);
+        }
+    }
+}
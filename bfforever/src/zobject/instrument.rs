@@ -0,0 +1,18 @@
+use bfforever_derive::{ZObjectData, ZObjectReader, ZObjectWriter};
+use crate::HKey;
+
+pub const INSTRUMENT_TYPE_GUITAR: &str = "guitar";
+pub const INSTRUMENT_TYPE_BASS: &str = "bass";
+pub const INSTRUMENT_TYPE_VOX: &str = "vox";
+
+#[derive(Clone, Debug, Default, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct Instrument {
+    pub instrument_type: HKey, // guitar, bass, vox
+    pub difficulty: HKey,
+    pub tuning: HKey,
+
+    pub start: f32, // Milliseconds
+    pub end: f32,
+
+    pub tracks: Vec<HKey>, // Paths to tab/vox/event objects
+}
@@ -1,15 +1,159 @@
 use crate::HKey;
+use crate::io::{ZObjectContext, ZObjectReader, ZObjectWriter};
+use std::io::{Error as IOError, Read, Seek, Write};
 
+mod catalog2;
+mod chord;
+mod errors;
+mod event;
+mod index2;
+mod instrument;
+mod named_event;
+mod package_def;
+mod section;
 mod song;
+mod tab;
+mod tempo;
+mod tempo_map;
+mod vox;
 
+pub use crate::io::ZObjectData;
+pub use catalog2::*;
+pub use chord::*;
+pub use errors::*;
+pub use event::*;
+pub use index2::*;
+pub use instrument::*;
+pub use named_event::*;
+pub use package_def::*;
+pub use section::*;
 #[allow(unused_imports)] pub use song::*;
+pub use tab::*;
+pub use tempo::*;
+pub use tempo_map::*;
+pub use vox::*;
 
+const CATALOG2_TYPE: &str = "Catalog2";
+const CHORD_TYPE: &str = "Chord";
+const EVENT_TYPE: &str = "Event";
+const INDEX2_TYPE: &str = "Index2";
+const INSTRUMENT_TYPE: &str = "Instrument";
+const MEASURE_TYPE: &str = "Measure";
+const PACKAGE_DEF_TYPE: &str = "PackageDef";
+const SECTION_TYPE: &str = "Section";
+const SONG_TYPE: &str = "Song";
+const TAB_TYPE: &str = "Tab";
+const TEMPO_TYPE: &str = "Tempo";
+const TIME_SIGNATURE_TYPE: &str = "TimeSignature";
+const VOX_TYPE: &str = "Vox";
+
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug)]
 pub enum ZObject {
+    Catalog2(Catalog2),
+    Chord(Chord),
+    Event(Event),
+    Index2(Index2),
+    Instrument(Instrument),
+    Measure(Measure),
+    PackageDef(PackageDef),
+    Section(Section),
     Song(Song),
+    Tab(Tab),
+    Tempo(Tempo),
+    TimeSignature(TimeSignature),
+    Vox(Vox),
+    Raw(RawZObject),
+}
+
+/// Object of unsupported type, kept as-is so it can be written back
+#[derive(Debug)]
+pub struct RawZObject {
+    pub type_key: HKey,
+    pub data: Vec<u8>,
 }
 
+#[derive(Debug)]
 pub struct ZObjectChunk {
     pub full_path: HKey,
     pub dir_path: HKey,
     pub object: ZObject,
-}
\ No newline at end of file
+}
+
+impl ZObject {
+    pub fn get_type_key(&self) -> HKey {
+        match self {
+            ZObject::Catalog2(_) => HKey::new(CATALOG2_TYPE),
+            ZObject::Chord(_) => HKey::new(CHORD_TYPE),
+            ZObject::Event(_) => HKey::new(EVENT_TYPE),
+            ZObject::Index2(_) => HKey::new(INDEX2_TYPE),
+            ZObject::Instrument(_) => HKey::new(INSTRUMENT_TYPE),
+            ZObject::Measure(_) => HKey::new(MEASURE_TYPE),
+            ZObject::PackageDef(_) => HKey::new(PACKAGE_DEF_TYPE),
+            ZObject::Section(_) => HKey::new(SECTION_TYPE),
+            ZObject::Song(_) => HKey::new(SONG_TYPE),
+            ZObject::Tab(_) => HKey::new(TAB_TYPE),
+            ZObject::Tempo(_) => HKey::new(TEMPO_TYPE),
+            ZObject::TimeSignature(_) => HKey::new(TIME_SIGNATURE_TYPE),
+            ZObject::Vox(_) => HKey::new(VOX_TYPE),
+            ZObject::Raw(raw) => raw.type_key,
+        }
+    }
+
+    pub fn is_raw(&self) -> bool {
+        matches!(self, ZObject::Raw(_))
+    }
+
+    pub(crate) fn read<T: Read + Seek>(type_key: HKey, reader: &mut T, ctx: &ZObjectContext) -> Result<ZObject, IOError> {
+        Ok(match type_key {
+            k if k.eq(&HKey::new(CATALOG2_TYPE)) => ZObject::Catalog2(read_object(reader, ctx)?),
+            k if k.eq(&HKey::new(CHORD_TYPE)) => ZObject::Chord(read_object(reader, ctx)?),
+            k if k.eq(&HKey::new(EVENT_TYPE)) => ZObject::Event(read_object(reader, ctx)?),
+            k if k.eq(&HKey::new(INDEX2_TYPE)) => ZObject::Index2(read_object(reader, ctx)?),
+            k if k.eq(&HKey::new(INSTRUMENT_TYPE)) => ZObject::Instrument(read_object(reader, ctx)?),
+            k if k.eq(&HKey::new(MEASURE_TYPE)) => ZObject::Measure(read_object(reader, ctx)?),
+            k if k.eq(&HKey::new(PACKAGE_DEF_TYPE)) => ZObject::PackageDef(read_object(reader, ctx)?),
+            k if k.eq(&HKey::new(SECTION_TYPE)) => ZObject::Section(read_object(reader, ctx)?),
+            k if k.eq(&HKey::new(SONG_TYPE)) => ZObject::Song(read_object(reader, ctx)?),
+            k if k.eq(&HKey::new(TAB_TYPE)) => ZObject::Tab(read_object(reader, ctx)?),
+            k if k.eq(&HKey::new(TEMPO_TYPE)) => ZObject::Tempo(read_object(reader, ctx)?),
+            k if k.eq(&HKey::new(TIME_SIGNATURE_TYPE)) => ZObject::TimeSignature(read_object(reader, ctx)?),
+            k if k.eq(&HKey::new(VOX_TYPE)) => ZObject::Vox(read_object(reader, ctx)?),
+            _ => {
+                let mut data = Vec::new();
+                reader.read_to_end(&mut data)?;
+
+                ZObject::Raw(RawZObject {
+                    type_key,
+                    data
+                })
+            }
+        })
+    }
+
+    pub(crate) fn write<T: Seek + Write>(&self, writer: &mut T, ctx: &ZObjectContext) -> Result<(), IOError> {
+        match self {
+            ZObject::Catalog2(catalog) => catalog.write(writer, ctx),
+            ZObject::Chord(chord) => chord.write(writer, ctx),
+            ZObject::Event(event) => event.write(writer, ctx),
+            ZObject::Index2(index) => index.write(writer, ctx),
+            ZObject::Instrument(instrument) => instrument.write(writer, ctx),
+            ZObject::Measure(measure) => measure.write(writer, ctx),
+            ZObject::PackageDef(package_def) => package_def.write(writer, ctx),
+            ZObject::Section(section) => section.write(writer, ctx),
+            ZObject::Song(song) => song.write(writer, ctx),
+            ZObject::Tab(tab) => tab.write(writer, ctx),
+            ZObject::Tempo(tempo) => tempo.write(writer, ctx),
+            ZObject::TimeSignature(time_signature) => time_signature.write(writer, ctx),
+            ZObject::Vox(vox) => vox.write(writer, ctx),
+            ZObject::Raw(raw) => writer.write_all(&raw.data),
+        }
+    }
+}
+
+fn read_object<Z: Default + ZObjectReader, T: Read + Seek>(reader: &mut T, ctx: &ZObjectContext) -> Result<Z, IOError> {
+    let mut obj = Z::default();
+    obj.read(reader, ctx)?;
+
+    Ok(obj)
+}
@@ -0,0 +1,65 @@
+use bfforever_derive::{ZObjectData, ZObjectReader, ZObjectWriter};
+use crate::{Localization, SKey};
+use crate::string_table::StringTables;
+
+/// Named time range shared by section, chord and event objects
+#[derive(Clone, Debug, Default, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct NamedEventEntry {
+    pub start: f32, // Milliseconds
+    pub end: f32,
+
+    pub name: SKey,
+}
+
+impl NamedEventEntry {
+    pub fn get_duration(&self) -> f32 {
+        self.end - self.start
+    }
+
+    pub fn get_name<'a>(&self, string_tables: &'a StringTables, localization: Localization) -> Option<&'a str> {
+        string_tables.get(&self.name, localization)
+    }
+}
+
+pub trait NamedEvents {
+    fn get_events(&self) -> &[NamedEventEntry];
+
+    /// Gets entry active at time (ms)
+    fn find_at(&self, time: f32) -> Option<&NamedEventEntry> {
+        self.get_events()
+            .iter()
+            .find(|e| time >= e.start && time < e.end)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::zobject::{Chord, Event, Section};
+
+    fn create_entries() -> Vec<NamedEventEntry> {
+        vec![
+            NamedEventEntry { start: 0.0, end: 1000.0, name: SKey { value: 1 } },
+            NamedEventEntry { start: 1000.0, end: 2500.0, name: SKey { value: 2 } },
+        ]
+    }
+
+    fn find_name<T: NamedEvents>(events: &T, time: f32) -> Option<u64> {
+        events.find_at(time).map(|e| e.name.value)
+    }
+
+    #[test]
+    fn find_at_is_consistent_for_all_types() {
+        let section = Section { events: create_entries() };
+        let chord = Chord { events: create_entries() };
+        let event = Event { events: create_entries() };
+
+        for (time, expected) in [(0.0, Some(1)), (999.0, Some(1)), (1000.0, Some(2)), (2500.0, None)] {
+            assert_eq!(expected, find_name(&section, time));
+            assert_eq!(expected, find_name(&chord, time));
+            assert_eq!(expected, find_name(&event, time));
+        }
+
+        assert_eq!(1500.0, create_entries()[1].get_duration());
+    }
+}
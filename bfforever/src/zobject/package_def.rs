@@ -0,0 +1,75 @@
+use bfforever_derive::{ZObjectData, ZObjectReader, ZObjectWriter};
+use crate::HKey;
+
+#[derive(Clone, Debug, Default, PartialEq, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct PackageDef {
+    pub entries: Vec<HKey>, // Package file paths
+}
+
+impl PackageDef {
+    pub fn contains_entry(&self, path: &HKey) -> bool {
+        self.entries.contains(path)
+    }
+
+    /// Adds entry if not already present
+    pub fn add_entry(&mut self, path: HKey) -> bool {
+        if self.contains_entry(&path) {
+            return false;
+        }
+
+        self.entries.push(path);
+        true
+    }
+
+    pub fn remove_entry(&mut self, path: &HKey) -> bool {
+        let count = self.entries.len();
+        self.entries.retain(|e| !e.eq(path));
+
+        self.entries.len() != count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::{ZObjectContext, ZObjectReader, ZObjectWriter};
+    use std::io::Cursor;
+
+    #[test]
+    fn add_and_remove_entries() {
+        let mut package_def = PackageDef::default();
+        let first = HKey::new("songs/test/test.rif");
+        let second = HKey::new("songs/test/update.rif");
+
+        assert!(package_def.add_entry(first));
+        assert!(package_def.add_entry(second));
+        assert!(!package_def.add_entry(first));
+        assert_eq!(vec![first, second], package_def.entries);
+
+        assert!(package_def.remove_entry(&first));
+        assert!(!package_def.remove_entry(&first));
+        assert!(!package_def.contains_entry(&first));
+        assert_eq!(vec![second], package_def.entries);
+    }
+
+    #[test]
+    fn round_trip_both_endians() {
+        let package_def = PackageDef {
+            entries: vec![HKey::new("songs/test/test.rif"), HKey::new("songs/test/update.rif")],
+        };
+
+        for big_endian in [false, true] {
+            let ctx = ZObjectContext::new(big_endian);
+
+            let mut stream = Cursor::new(Vec::new());
+            package_def.write(&mut stream, &ctx).unwrap();
+            assert_eq!(8 + (2 * 8), stream.get_ref().len());
+
+            stream.set_position(0);
+            let mut read_package_def = PackageDef::default();
+            read_package_def.read(&mut stream, &ctx).unwrap();
+
+            assert_eq!(package_def, read_package_def);
+        }
+    }
+}
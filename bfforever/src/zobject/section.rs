@@ -0,0 +1,15 @@
+use bfforever_derive::{ZObjectData, ZObjectReader, ZObjectWriter};
+use super::{NamedEventEntry, NamedEvents};
+
+pub type SectionEntry = NamedEventEntry;
+
+#[derive(Clone, Debug, Default, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct Section {
+    pub events: Vec<SectionEntry>,
+}
+
+impl NamedEvents for Section {
+    fn get_events(&self) -> &[NamedEventEntry] {
+        &self.events
+    }
+}
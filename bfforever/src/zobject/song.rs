@@ -1,7 +1,7 @@
-use bfforever_derive::ZObjectReader;
+use bfforever_derive::{ZObjectData, ZObjectReader, ZObjectWriter};
 use crate::{HKey, SKey};
 
-#[derive(ZObjectReader)]
+#[derive(Clone, Debug, Default, PartialEq, ZObjectData, ZObjectReader, ZObjectWriter)]
 pub struct Song {
     pub title: SKey,
     pub artist: SKey,
@@ -21,12 +21,12 @@ pub struct Song {
     pub labels: Vec<SKey>,
 
     pub song_length: f32,
-    // Empty 4 bytes
 
+    #[zobject(skip = 4)]
     pub preview_path: HKey,
     pub video_path: HKey,
-    // Empty 8 bytes
 
+    #[zobject(skip = 8)]
     pub instrument_tags: Vec<HKey>,
 
     pub backing_audio_path: HKey,
@@ -35,4 +35,82 @@ pub struct Song {
     pub lead_guitar_audio_path: HKey,
     pub rhythm_guitar_audio_path: HKey,
     pub vox_audio_path: HKey,
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::io::{ZObjectContext, ZObjectReader, ZObjectWriter};
+    use std::io::Cursor;
+
+    fn create_song() -> Song {
+        Song {
+            title: SKey { value: 0x0101010101010101 },
+            artist: SKey { value: 0x0202020202020202 },
+            description: SKey { value: 0x0303030303030303 },
+            album: SKey { value: 0x0404040404040404 },
+            texture_path: HKey { value: 0x0505050505050505 },
+            legend_tag: HKey { value: 0x0606060606060606 },
+            era_tag: HKey { value: 0x0707070707070707 },
+            year: 1977,
+            guitar_intensity: 0.25,
+            bass_intensity: 0.5,
+            vox_intensity: 0.75,
+            metadata_tags: vec![HKey { value: 0x11 }, HKey { value: 0x12 }],
+            genre_tags: vec![HKey { value: 0x21 }],
+            labels: vec![SKey { value: 0x31 }, SKey { value: 0x32 }, SKey { value: 0x33 }],
+            song_length: 245.5,
+            preview_path: HKey { value: 0x0808080808080808 },
+            video_path: HKey { value: 0x0909090909090909 },
+            instrument_tags: vec![HKey { value: 0x41 }],
+            backing_audio_path: HKey { value: 0x51 },
+            bass_audio_path: HKey { value: 0x52 },
+            drums_audio_path: HKey { value: 0x53 },
+            lead_guitar_audio_path: HKey { value: 0x54 },
+            rhythm_guitar_audio_path: HKey { value: 0x55 },
+            vox_audio_path: HKey { value: 0x56 },
+        }
+    }
+
+    fn assert_round_trip(big_endian: bool) {
+        let ctx = ZObjectContext::new(big_endian);
+        let song = create_song();
+
+        let mut stream = Cursor::new(Vec::new());
+        song.write(&mut stream, &ctx).unwrap();
+        let data = stream.into_inner();
+
+        // Keys + numbers, tags + labels, length, skip, preview + video, skip, instrument tags, audio paths
+        let song_length_offset = 72 + (8 + 16) + (8 + 8) + (8 + 24);
+        assert_eq!(song_length_offset + 4 + 4 + 16 + 8 + (8 + 8) + 48, data.len());
+
+        assert_eq!(&[0u8; 4], &data[(song_length_offset + 4)..(song_length_offset + 8)]);
+        assert_eq!(&[0u8; 8], &data[(song_length_offset + 24)..(song_length_offset + 32)]);
+
+        let year_bytes = match big_endian {
+            true => 1977u32.to_be_bytes(),
+            _ => 1977u32.to_le_bytes(),
+        };
+        assert_eq!(&year_bytes, &data[56..60]);
+
+        // Skipped bytes should be ignored when reading
+        let mut data = data;
+        data[(song_length_offset + 4)..(song_length_offset + 8)].fill(0xFF);
+        data[(song_length_offset + 24)..(song_length_offset + 32)].fill(0xFF);
+
+        let mut read_song = Song::default();
+        read_song.read(&mut Cursor::new(data), &ctx).unwrap();
+
+        assert_eq!(song, read_song);
+    }
+
+    #[test]
+    fn round_trip_little_endian() {
+        assert_round_trip(false);
+    }
+
+    #[test]
+    fn round_trip_big_endian() {
+        assert_round_trip(true);
+    }
+}
@@ -0,0 +1,39 @@
+use bfforever_derive::{ZObjectData, ZObjectReader, ZObjectWriter};
+
+// Technique flags
+pub const TECHNIQUE_HAMMER_ON: u32  = 1 << 0;
+pub const TECHNIQUE_PULL_OFF: u32   = 1 << 1;
+pub const TECHNIQUE_SLIDE_UP: u32   = 1 << 2;
+pub const TECHNIQUE_SLIDE_DOWN: u32 = 1 << 3;
+pub const TECHNIQUE_BEND: u32       = 1 << 4;
+pub const TECHNIQUE_VIBRATO: u32    = 1 << 5;
+pub const TECHNIQUE_PALM_MUTE: u32  = 1 << 6;
+pub const TECHNIQUE_MUTED: u32      = 1 << 7;
+pub const TECHNIQUE_HARMONIC: u32   = 1 << 8;
+pub const TECHNIQUE_TAP: u32        = 1 << 9;
+
+#[derive(Clone, Debug, Default, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct TabEntry {
+    pub start: f32, // Milliseconds
+    pub end: f32,
+
+    pub string: u32, // 0 = lowest string
+    pub fret: u32,
+    pub finger: u32,
+    pub techniques: u32, // TECHNIQUE_* flags
+}
+
+#[derive(Clone, Debug, Default, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct Tab {
+    pub events: Vec<TabEntry>,
+}
+
+impl TabEntry {
+    pub fn get_duration(&self) -> f32 {
+        self.end - self.start
+    }
+
+    pub fn has_technique(&self, technique: u32) -> bool {
+        (self.techniques & technique) != 0
+    }
+}
@@ -0,0 +1,39 @@
+use bfforever_derive::{ZObjectData, ZObjectReader, ZObjectWriter};
+
+#[derive(Clone, Debug, Default, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct TempoEntry {
+    pub start: f32, // Milliseconds
+    pub end: f32,
+
+    pub qbpm: f32, // Quarter notes per minute
+}
+
+#[derive(Clone, Debug, Default, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct Tempo {
+    pub events: Vec<TempoEntry>,
+}
+
+#[derive(Clone, Debug, Default, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct TimeSignatureEntry {
+    pub start: f32, // Milliseconds
+    pub end: f32,
+
+    pub numerator: u32,   // Beats per measure
+    pub denominator: u32, // Beat note value
+}
+
+#[derive(Clone, Debug, Default, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct TimeSignature {
+    pub events: Vec<TimeSignatureEntry>,
+}
+
+#[derive(Clone, Debug, Default, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct MeasureEntry {
+    pub start: f32, // Milliseconds
+    pub end: f32,
+}
+
+#[derive(Clone, Debug, Default, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct Measure {
+    pub events: Vec<MeasureEntry>,
+}
@@ -0,0 +1,460 @@
+use super::{Measure, MeasureEntry, Tempo, TempoEntry, TempoMapError, TimeSignature, TimeSignatureEntry};
+
+pub const DEFAULT_RESOLUTION: u32 = 480; // Ticks per quarter note
+pub const DEFAULT_QBPM: f64 = 120.0;
+
+#[derive(Clone, Copy, Debug)]
+pub struct TempoChange {
+    pub tick: u64,
+    pub seconds: f64,
+    pub qbpm: f64,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct TimeSignatureChange {
+    pub tick: u64,
+    pub measure: u32,
+    pub numerator: u32,
+    pub denominator: u32,
+}
+
+/// Converts between ticks, seconds and measure/beat positions
+#[derive(Clone, Debug)]
+pub struct TempoMap {
+    resolution: u32,
+    tempos: Vec<TempoChange>,
+    time_signatures: Vec<TimeSignatureChange>,
+}
+
+impl Default for TempoMap {
+    fn default() -> TempoMap {
+        TempoMap::new(DEFAULT_RESOLUTION)
+    }
+}
+
+impl TimeSignatureChange {
+    pub fn get_ticks_per_beat(&self, resolution: u32) -> u64 {
+        ((resolution as u64) * 4) / (self.denominator.max(1) as u64)
+    }
+
+    pub fn get_ticks_per_measure(&self, resolution: u32) -> u64 {
+        self.get_ticks_per_beat(resolution) * (self.numerator.max(1) as u64)
+    }
+}
+
+impl TempoMap {
+    /// Creates map at 120 bpm and 4/4
+    pub fn new(resolution: u32) -> TempoMap {
+        TempoMap {
+            resolution: resolution.max(1),
+            tempos: vec![TempoChange { tick: 0, seconds: 0.0, qbpm: DEFAULT_QBPM }],
+            time_signatures: vec![TimeSignatureChange { tick: 0, measure: 0, numerator: 4, denominator: 4 }],
+        }
+    }
+
+    pub fn from_zobjects(tempo: &Tempo, time_signature: &TimeSignature, resolution: u32) -> Result<TempoMap, TempoMapError> {
+        let mut map = TempoMap::new(resolution);
+
+        let mut tempo_entries = tempo.events.iter().collect::<Vec<_>>();
+        tempo_entries.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+        // Default tempo applies until first entry
+        let mut prev = (0u64, 0.0f64, DEFAULT_QBPM); // (tick, seconds, qbpm)
+
+        for entry in tempo_entries {
+            let (prev_tick, prev_seconds, prev_qbpm) = prev;
+            let seconds = entry.start as f64 / 1000.0;
+            let tick = prev_tick + seconds_to_ticks(seconds - prev_seconds, prev_qbpm, map.resolution);
+
+            map.add_tempo(tick, entry.qbpm as f64)?;
+            prev = (tick, seconds, entry.qbpm as f64);
+        }
+
+        let mut ts_entries = time_signature.events.iter().collect::<Vec<_>>();
+        ts_entries.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+        for entry in ts_entries {
+            let tick = map.seconds_to_tick(entry.start as f64 / 1000.0);
+            map.add_time_signature(tick, entry.numerator, entry.denominator)?;
+        }
+
+        Ok(map)
+    }
+
+    pub fn get_resolution(&self) -> u32 {
+        self.resolution
+    }
+
+    pub fn get_tempos(&self) -> &[TempoChange] {
+        &self.tempos
+    }
+
+    pub fn get_time_signatures(&self) -> &[TimeSignatureChange] {
+        &self.time_signatures
+    }
+
+    /// Adds tempo change, replacing any existing change at same tick
+    pub fn add_tempo(&mut self, tick: u64, qbpm: f64) -> Result<(), TempoMapError> {
+        if !qbpm.is_finite() || qbpm <= 0.0 {
+            return Err(TempoMapError::InvalidTempo { qbpm });
+        }
+
+        self.tempos.retain(|t| t.tick != tick);
+
+        let index = self.tempos.partition_point(|t| t.tick < tick);
+        self.tempos.insert(index, TempoChange { tick, seconds: 0.0, qbpm });
+
+        self.update_tempo_seconds();
+        Ok(())
+    }
+
+    /// Adds time signature change, replacing any existing change at same tick
+    pub fn add_time_signature(&mut self, tick: u64, numerator: u32, denominator: u32) -> Result<(), TempoMapError> {
+        let resolution = self.resolution;
+
+        // Beat must be at least 1 tick long
+        if numerator == 0
+            || !denominator.is_power_of_two()
+            || ((resolution as u64) * 4) / (denominator as u64) == 0 {
+            return Err(TempoMapError::InvalidTimeSignature { numerator, denominator, resolution });
+        }
+
+        self.time_signatures.retain(|ts| ts.tick != tick);
+
+        let index = self.time_signatures.partition_point(|ts| ts.tick < tick);
+        self.time_signatures.insert(index, TimeSignatureChange { tick, measure: 0, numerator, denominator });
+
+        self.update_measures();
+        Ok(())
+    }
+
+    pub fn tick_to_seconds(&self, tick: u64) -> f64 {
+        let tempo = self.get_tempo_at_tick(tick);
+        tempo.seconds + ticks_to_seconds(tick - tempo.tick, tempo.qbpm, self.resolution)
+    }
+
+    pub fn seconds_to_tick(&self, seconds: f64) -> u64 {
+        let tempo = self.tempos
+            .iter()
+            .rev()
+            .find(|t| t.seconds <= seconds)
+            .unwrap_or(&self.tempos[0]);
+
+        tempo.tick + seconds_to_ticks(seconds - tempo.seconds, tempo.qbpm, self.resolution)
+    }
+
+    pub fn tick_to_milliseconds(&self, tick: u64) -> f32 {
+        (self.tick_to_seconds(tick) * 1000.0) as f32
+    }
+
+    pub fn milliseconds_to_tick(&self, ms: f32) -> u64 {
+        self.seconds_to_tick(ms as f64 / 1000.0)
+    }
+
+    /// Gets 0-based measure and beat (fractional) of tick
+    pub fn tick_to_measure_beat(&self, tick: u64) -> (u32, f64) {
+        let ts = self.get_time_signature_at_tick(tick);
+
+        let ticks_per_measure = ts.get_ticks_per_measure(self.resolution);
+        let ticks_per_beat = ts.get_ticks_per_beat(self.resolution);
+        let delta = tick - ts.tick;
+
+        let measure = ts.measure + (delta / ticks_per_measure) as u32;
+        let beat = (delta % ticks_per_measure) as f64 / ticks_per_beat as f64;
+
+        (measure, beat)
+    }
+
+    pub fn measure_beat_to_tick(&self, measure: u32, beat: f64) -> u64 {
+        let ts = self.time_signatures
+            .iter()
+            .rev()
+            .find(|ts| ts.measure <= measure)
+            .unwrap_or(&self.time_signatures[0]);
+
+        let ticks_per_measure = ts.get_ticks_per_measure(self.resolution);
+        let ticks_per_beat = ts.get_ticks_per_beat(self.resolution);
+
+        ts.tick
+            + ((measure - ts.measure) as u64 * ticks_per_measure)
+            + (beat.max(0.0) * ticks_per_beat as f64).round() as u64
+    }
+
+    pub fn get_measure_start_tick(&self, measure: u32) -> u64 {
+        self.measure_beat_to_tick(measure, 0.0)
+    }
+
+    pub fn to_tempo(&self, end_seconds: f64) -> Tempo {
+        let events = self.tempos
+            .iter()
+            .enumerate()
+            .map(|(i, t)| TempoEntry {
+                start: (t.seconds * 1000.0) as f32,
+                end: (self.tempos.get(i + 1).map(|n| n.seconds).unwrap_or(end_seconds) * 1000.0) as f32,
+                qbpm: t.qbpm as f32,
+            })
+            .collect();
+
+        Tempo { events }
+    }
+
+    pub fn to_time_signature(&self, end_seconds: f64) -> TimeSignature {
+        let events = self.time_signatures
+            .iter()
+            .enumerate()
+            .map(|(i, ts)| TimeSignatureEntry {
+                start: self.tick_to_milliseconds(ts.tick),
+                end: self.time_signatures
+                    .get(i + 1)
+                    .map(|n| self.tick_to_milliseconds(n.tick))
+                    .unwrap_or((end_seconds * 1000.0) as f32),
+                numerator: ts.numerator,
+                denominator: ts.denominator,
+            })
+            .collect();
+
+        TimeSignature { events }
+    }
+
+    /// Creates measure entries up to end time
+    pub fn to_measures(&self, end_seconds: f64) -> Measure {
+        let mut events = Vec::new();
+
+        if !end_seconds.is_finite() {
+            return Measure { events };
+        }
+
+        let mut measure = 0;
+        let mut start_tick = self.get_measure_start_tick(measure);
+
+        loop {
+            let start = self.tick_to_seconds(start_tick);
+
+            if start >= end_seconds {
+                break;
+            }
+
+            let end_tick = self.get_measure_start_tick(measure + 1);
+
+            if end_tick <= start_tick {
+                // Measure position stopped advancing
+                break;
+            }
+
+            let end = self.tick_to_seconds(end_tick);
+
+            events.push(MeasureEntry {
+                start: (start * 1000.0) as f32,
+                end: (end.min(end_seconds) * 1000.0) as f32,
+            });
+
+            measure += 1;
+            start_tick = end_tick;
+        }
+
+        Measure { events }
+    }
+
+    fn get_tempo_at_tick(&self, tick: u64) -> &TempoChange {
+        self.tempos
+            .iter()
+            .rev()
+            .find(|t| t.tick <= tick)
+            .unwrap_or(&self.tempos[0])
+    }
+
+    fn get_time_signature_at_tick(&self, tick: u64) -> &TimeSignatureChange {
+        self.time_signatures
+            .iter()
+            .rev()
+            .find(|ts| ts.tick <= tick)
+            .unwrap_or(&self.time_signatures[0])
+    }
+
+    fn update_tempo_seconds(&mut self) {
+        let resolution = self.resolution;
+        let mut prev: Option<TempoChange> = None;
+
+        for tempo in self.tempos.iter_mut() {
+            tempo.seconds = match prev {
+                Some(p) => p.seconds + ticks_to_seconds(tempo.tick - p.tick, p.qbpm, resolution),
+                _ => ticks_to_seconds(tempo.tick, DEFAULT_QBPM, resolution),
+            };
+
+            prev = Some(*tempo);
+        }
+    }
+
+    fn update_measures(&mut self) {
+        let resolution = self.resolution;
+        let mut prev: Option<TimeSignatureChange> = None;
+
+        for ts in self.time_signatures.iter_mut() {
+            ts.measure = match prev {
+                Some(p) => {
+                    // Partial measures are counted as full measure
+                    let ticks_per_measure = p.get_ticks_per_measure(resolution);
+                    p.measure + (ts.tick - p.tick).div_ceil(ticks_per_measure.max(1)) as u32
+                },
+                _ => 0,
+            };
+
+            prev = Some(*ts);
+        }
+    }
+}
+
+fn ticks_to_seconds(ticks: u64, qbpm: f64, resolution: u32) -> f64 {
+    (ticks as f64 / resolution as f64) * (60.0 / qbpm)
+}
+
+fn seconds_to_ticks(seconds: f64, qbpm: f64, resolution: u32) -> u64 {
+    ((seconds.max(0.0) * (qbpm / 60.0)) * resolution as f64).round() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_seconds(expected: f64, actual: f64) {
+        assert!((expected - actual).abs() < 1e-9, "Expected {expected} seconds, got {actual}");
+    }
+
+    #[test]
+    fn default_map_is_120_bpm() {
+        let map = TempoMap::default();
+
+        assert_seconds(0.5, map.tick_to_seconds(480));
+        assert_seconds(2.0, map.tick_to_seconds(1920));
+        assert_eq!(1440, map.seconds_to_tick(1.5));
+        assert_eq!(1500.0, map.tick_to_milliseconds(1440));
+        assert_eq!(1440, map.milliseconds_to_tick(1500.0));
+    }
+
+    #[test]
+    fn tempo_changes_convert_ticks_and_seconds() {
+        let mut map = TempoMap::new(480);
+        map.add_tempo(1920, 60.0).unwrap(); // After 4 beats at 120 bpm
+        map.add_tempo(2880, 240.0).unwrap(); // After 2 beats at 60 bpm
+
+        assert_seconds(2.0, map.get_tempos()[1].seconds);
+        assert_seconds(4.0, map.get_tempos()[2].seconds);
+
+        assert_seconds(3.0, map.tick_to_seconds(2400));
+        assert_seconds(4.25, map.tick_to_seconds(3360));
+        assert_eq!(2400, map.seconds_to_tick(3.0));
+        assert_eq!(3360, map.seconds_to_tick(4.25));
+    }
+
+    #[test]
+    fn time_signature_changes_convert_measure_beats() {
+        let mut map = TempoMap::new(480);
+        map.add_time_signature(3840, 3, 4).unwrap(); // Measure 2
+        map.add_time_signature(5280, 6, 8).unwrap(); // Measure 3
+
+        let measures = map.get_time_signatures()
+            .iter()
+            .map(|ts| ts.measure)
+            .collect::<Vec<_>>();
+        assert_eq!(vec![0, 2, 3], measures);
+
+        assert_eq!((0, 3.0), map.tick_to_measure_beat(1440));
+        assert_eq!((2, 2.5), map.tick_to_measure_beat(3840 + 1200));
+        assert_eq!((4, 1.0), map.tick_to_measure_beat(5280 + 1440 + 240));
+
+        assert_eq!(3840 + 960, map.measure_beat_to_tick(2, 2.0));
+        assert_eq!(5280 + 1440 + 240, map.measure_beat_to_tick(4, 1.0));
+        assert_eq!(1920, map.get_measure_start_tick(1));
+    }
+
+    #[test]
+    fn partial_measure_counts_as_full_measure() {
+        let mut map = TempoMap::new(480);
+        map.add_time_signature(2000, 3, 4).unwrap();
+
+        assert_eq!(2, map.get_time_signatures()[1].measure);
+        assert_eq!(2000, map.get_measure_start_tick(2));
+    }
+
+    #[test]
+    fn invalid_time_signatures_are_rejected() {
+        let mut map = TempoMap::new(480);
+
+        assert!(map.add_time_signature(0, 0, 4).is_err());
+        assert!(map.add_time_signature(0, 4, 0).is_err());
+        assert!(map.add_time_signature(0, 4, 3).is_err());
+        assert!(map.add_time_signature(0, 4, 4096).is_err()); // Beat shorter than 1 tick
+        assert!(map.add_time_signature(0, 4, 1024).is_ok());
+
+        assert_eq!(1, map.get_time_signatures().len());
+    }
+
+    #[test]
+    fn invalid_tempos_are_rejected() {
+        let mut map = TempoMap::new(480);
+
+        assert!(map.add_tempo(0, 0.0).is_err());
+        assert!(map.add_tempo(0, -120.0).is_err());
+        assert!(map.add_tempo(0, f64::NAN).is_err());
+        assert!(map.add_tempo(0, f64::INFINITY).is_err());
+
+        assert_eq!(DEFAULT_QBPM, map.get_tempos()[0].qbpm);
+    }
+
+    #[test]
+    fn to_measures_stops_at_end() {
+        let map = TempoMap::new(480);
+        let measures = map.to_measures(5.0);
+
+        let times = measures.events
+            .iter()
+            .map(|m| (m.start, m.end))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(0.0, 2000.0), (2000.0, 4000.0), (4000.0, 5000.0)], times);
+
+        assert!(map.to_measures(f64::NAN).events.is_empty());
+        assert!(map.to_measures(f64::INFINITY).events.is_empty());
+        assert!(map.to_measures(0.0).events.is_empty());
+    }
+
+    #[test]
+    fn from_zobjects_keeps_first_tempo_start() {
+        let tempo = Tempo {
+            events: vec![
+                TempoEntry { start: 1000.0, end: 3000.0, qbpm: 60.0 },
+                TempoEntry { start: 3000.0, end: 4000.0, qbpm: 120.0 },
+            ]
+        };
+        let time_signature = TimeSignature {
+            events: vec![
+                TimeSignatureEntry { start: 0.0, end: 3000.0, numerator: 4, denominator: 4 },
+                TimeSignatureEntry { start: 3000.0, end: 4000.0, numerator: 7, denominator: 8 },
+            ]
+        };
+
+        let map = TempoMap::from_zobjects(&tempo, &time_signature, 480).unwrap();
+
+        // Default tempo applies before first entry
+        let ticks = map.get_tempos()
+            .iter()
+            .map(|t| (t.tick, t.qbpm))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![(0, 120.0), (960, 60.0), (1920, 120.0)], ticks);
+        assert_eq!(1920, map.get_time_signatures()[1].tick);
+
+        // Round trip back to zobjects
+        let new_tempo = map.to_tempo(4.0);
+        assert_eq!(3, new_tempo.events.len());
+        assert_eq!(1000.0, new_tempo.events[1].start);
+        assert_eq!(3000.0, new_tempo.events[2].start);
+    }
+
+    #[test]
+    fn from_zobjects_rejects_invalid_time_signature() {
+        let time_signature = TimeSignature {
+            events: vec![TimeSignatureEntry { start: 0.0, end: 1000.0, numerator: 4, denominator: 4096 }]
+        };
+
+        assert!(TempoMap::from_zobjects(&Tempo::default(), &time_signature, 480).is_err());
+    }
+}
@@ -0,0 +1,22 @@
+use bfforever_derive::{ZObjectData, ZObjectReader, ZObjectWriter};
+use crate::SKey;
+
+#[derive(Clone, Debug, Default, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct VoxEntry {
+    pub start: f32, // Milliseconds
+    pub end: f32,
+
+    pub pitch: u32, // Midi note
+    pub lyric: SKey,
+}
+
+#[derive(Clone, Debug, Default, ZObjectData, ZObjectReader, ZObjectWriter)]
+pub struct Vox {
+    pub events: Vec<VoxEntry>,
+}
+
+impl VoxEntry {
+    pub fn get_duration(&self) -> f32 {
+        self.end - self.start
+    }
+}
@@ -1,34 +1,192 @@
 use proc_macro::TokenStream;
-use syn::{Data, DataStruct, DeriveInput, Field, Fields, Meta, Path, parse::Parser, parse_macro_input, punctuated::Punctuated, Token, Type};
+use proc_macro2::TokenStream as TokenStream2;
+use syn::{Data, DeriveInput, Error, Field, Fields, LitInt, parse_macro_input, Type};
 use quote::quote;
 
-#[proc_macro_derive(ZObjectReader)]
+#[derive(Default)]
+struct FieldOptions {
+    skip: Option<usize>,
+    big_endian: bool,
+    since_version: Option<u32>,
+    count: Option<Type>,
+}
+
+#[proc_macro_derive(ZObjectReader, attributes(zobject))]
 pub fn derive_zobjectreader(tokens: TokenStream) -> TokenStream {
     let input = parse_macro_input!(tokens as DeriveInput);
 
-    let fields = get_struct_fields(&input);
-    
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let reads = get_struct_fields(&input)
+        .into_iter()
+        .map(|field| {
+            let ident = &field.ident;
+            let ty = &field.ty;
+            let options = get_field_options(field)?;
+
+            let ctx = match options.big_endian {
+                true => quote! { &ctx.with_big_endian() },
+                _ => quote! { ctx },
+            };
+
+            let skip = options.skip.map(|skip| {
+                let skip = skip as i64;
+                quote! { reader.seek(std::io::SeekFrom::Current(#skip))?; }
+            });
+
+            let read = match &options.count {
+                Some(count_ty) => quote! {
+                    self.#ident = crate::io::read_vec_with_count::<#count_ty, _, _>(reader, #ctx)?;
+                },
+                _ => quote! {
+                    self.#ident = <#ty as crate::io::ZObjectField>::read_field(reader, #ctx)?;
+                },
+            };
+
+            Ok(wrap_versioned(&options, quote! { #skip #read }))
+        })
+        .collect::<Result<Vec<_>, Error>>();
+
+    let reads = match reads {
+        Ok(reads) => reads,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    quote! {
+        impl #impl_generics crate::io::ZObjectReader for #name #ty_generics #where_clause {
+            fn read<T: std::io::Read + std::io::Seek>(&mut self, reader: &mut T, ctx: &crate::io::ZObjectContext) -> Result<(), std::io::Error> {
+                #(#reads)*
+                Ok(())
+            }
+        }
+    }.into()
+}
+
+#[proc_macro_derive(ZObjectWriter, attributes(zobject))]
+pub fn derive_zobjectwriter(tokens: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(tokens as DeriveInput);
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let writes = get_struct_fields(&input)
+        .into_iter()
+        .map(|field| {
+            let ident = &field.ident;
+            let ty = &field.ty;
+            let options = get_field_options(field)?;
+
+            let ctx = match options.big_endian {
+                true => quote! { &ctx.with_big_endian() },
+                _ => quote! { ctx },
+            };
+
+            let skip = options.skip.map(|skip| {
+                quote! { writer.write_all(&[0u8; #skip])?; }
+            });
+
+            let write = match &options.count {
+                Some(count_ty) => quote! {
+                    crate::io::write_vec_with_count::<#count_ty, _, _>(&self.#ident, writer, #ctx)?;
+                },
+                _ => quote! {
+                    <#ty as crate::io::ZObjectField>::write_field(&self.#ident, writer, #ctx)?;
+                },
+            };
+
+            Ok(wrap_versioned(&options, quote! { #skip #write }))
+        })
+        .collect::<Result<Vec<_>, Error>>();
+
+    let writes = match writes {
+        Ok(writes) => writes,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    quote! {
+        impl #impl_generics crate::io::ZObjectWriter for #name #ty_generics #where_clause {
+            fn write<T: std::io::Seek + std::io::Write>(&self, writer: &mut T, ctx: &crate::io::ZObjectContext) -> Result<(), std::io::Error> {
+                #(#writes)*
+                Ok(())
+            }
+        }
+    }.into()
+}
+
+#[proc_macro_derive(ZObjectData, attributes(zobject))]
+pub fn derive_zobjectdata(tokens: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(tokens as DeriveInput);
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let idents = get_struct_fields(&input)
+        .into_iter()
+        .map(|field| &field.ident)
+        .collect::<Vec<_>>();
+
+    quote! {
+        impl #impl_generics crate::io::ZObjectData for #name #ty_generics #where_clause {
+            fn get_all_string_keys(&self) -> Vec<&dyn crate::StringKey> {
+                let mut keys = Vec::new();
+                #(crate::io::ZObjectField::collect_string_keys(&self.#idents, &mut keys);)*
+                keys
+            }
 
-    for field in fields {
-        let att = field.ident.as_ref().map(|i| i.to_string()).unwrap_or_default();
-        let typ;
+            fn get_hkeys(&self) -> Vec<&crate::HKey> {
+                let mut keys = Vec::new();
+                #(crate::io::ZObjectField::collect_hkeys(&self.#idents, &mut keys);)*
+                keys
+            }
 
-        match &field.ty {
-            Type::Path(path) => {
-                typ = path.path.segments.last().as_ref().map(|s| s.ident.to_string()).unwrap_or_default();
-            },
-            _ => unimplemented!()
+            fn get_skeys(&self) -> Vec<&crate::SKey> {
+                let mut keys = Vec::new();
+                #(crate::io::ZObjectField::collect_skeys(&self.#idents, &mut keys);)*
+                keys
+            }
         }
+    }.into()
+}
 
-        println!("{att} ({typ})");
+fn wrap_versioned(options: &FieldOptions, tokens: TokenStream2) -> TokenStream2 {
+    match options.since_version {
+        Some(version) => quote! {
+            if ctx.version >= #version {
+                #tokens
+            }
+        },
+        _ => tokens,
     }
+}
 
-    //"fn answer() -> u32 { 42 }".parse().unwrap()
+fn get_field_options(field: &Field) -> Result<FieldOptions, Error> {
+    let mut options = FieldOptions::default();
 
-    quote!().into()
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("zobject")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                let lit: LitInt = meta.value()?.parse()?;
+                options.skip = Some(lit.base10_parse()?);
+            } else if meta.path.is_ident("big_endian") {
+                options.big_endian = true;
+            } else if meta.path.is_ident("since_version") {
+                let lit: LitInt = meta.value()?.parse()?;
+                options.since_version = Some(lit.base10_parse()?);
+            } else if meta.path.is_ident("count") {
+                options.count = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error("Unsupported zobject attribute"));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    Ok(options)
 }
 
-fn get_struct_fields<'a>(input: &'a DeriveInput) -> Vec<&'a Field> {
+fn get_struct_fields(input: &DeriveInput) -> Vec<&Field> {
     let Data::Struct(data_struct) = &input.data else {
         panic!("Only structs are supported")
     };
@@ -41,4 +199,4 @@ fn get_struct_fields<'a>(input: &'a DeriveInput) -> Vec<&'a Field> {
         .named
         .iter()
         .collect()
-}
\ No newline at end of file
+}
@@ -1,38 +1,93 @@
 use crate::apps::SubApp;
 use bfforever::audio::*;
 use clap::Parser;
-use log::debug;
+use log::{debug, error};
+use std::error::Error;
 
-const SUPPORTED_EXTS: [&'static str; 1] = [
+const SUPPORTED_EXTS: [&str; 1] = [
     ".wav",
     //".ogg",
 ];
 
 #[derive(Parser)]
 pub struct AudioApp {
-    #[arg(help = "Path to input audio file (clt/wav)", required = true)]
+    #[arg(help = "Path to input audio file (clt/wav/ogg)", required = true)]
     pub input_path: String,
-    #[arg(help = "Path to output audio file (clt/wav)", required = true)]
+    #[arg(help = "Path to output audio file (clt/wav/ogg)", required = true)]
     pub output_path: String,
+    #[arg(short, long, help = "Encrypt output audio (clt)")]
+    pub encrypt: bool,
 }
 
 impl SubApp for AudioApp {
     fn process(&mut self) {
         // clt -> clt - decrypt
         // clt -> wav - decode
+        // clt -> ogg - remux
         // wav -> clt - encode
+        // ogg -> clt - remux
         debug!("Processing audio: {}", &self.input_path);
 
+        let result = match &self.input_path {
+            p if p.ends_with(".wav") => self.encode_wav(),
+            p if p.ends_with(".ogg") || p.ends_with(".opus") => self.import_ogg_opus(),
+            _ => self.convert_celt(),
+        };
+
+        if let Err(err) = result {
+            error!("Unable to process \"{}\": {}", &self.input_path, err);
+            return;
+        }
+
+        print!("Wrote output to \"{}\"", &self.output_path);
+    }
+}
+
+impl AudioApp {
+    fn encode_wav(&self) -> Result<(), Box<dyn Error>> {
+        let wav_audio = WavDecoder::from_file(&self.input_path)?;
+        let samples = wav_audio.decode()?;
+
+        let options = CeltEncoderOptions {
+            encrypt: self.encrypt,
+            ..Default::default()
+        };
+
+        let celt_audio = Celt::encode_with_options(&samples, wav_audio.get_channels() as u32, wav_audio.get_sample_rate(), &options)?;
+        celt_audio.save(&self.output_path)?;
+
+        Ok(())
+    }
+
+    fn import_ogg_opus(&self) -> Result<(), Box<dyn Error>> {
+        let mut celt_audio = Celt::open_ogg_opus(&self.input_path)?;
+
+        if self.encrypt {
+            celt_audio.encrypt()?;
+        }
+
+        celt_audio.save(&self.output_path)?;
+        Ok(())
+    }
+
+    fn convert_celt(&self) -> Result<(), Box<dyn Error>> {
         // Assume input is celt
-        let mut celt_audio = Celt::open(&self.input_path);
-        celt_audio.decrypt();
+        let mut celt_audio = Celt::open(&self.input_path)?;
+        celt_audio.decrypt()?;
 
         if self.output_path.ends_with(".clt") {
-            // Save as decrypted .clt
-            celt_audio.save(&self.output_path);
+            // Save as .clt (decrypted unless requested)
+            if self.encrypt {
+                celt_audio.encrypt()?;
+            }
+
+            celt_audio.save(&self.output_path)?;
+        } else if self.output_path.ends_with(".ogg") || self.output_path.ends_with(".opus") {
+            // Copy packets to ogg opus
+            celt_audio.save_ogg_opus(&self.output_path)?;
         } else {
             // Decode and save as .wav
-            let samples = celt_audio.decode();
+            let samples = celt_audio.decode()?;
             let channels = celt_audio.get_channels() as u16;
             let sample_rate = celt_audio.get_sample_rate() as u32;
 
@@ -40,6 +95,6 @@ impl SubApp for AudioApp {
             wav_encoder.encode_to_file(&self.output_path);
         }
 
-        print!("Wrote output to \"{}\"", &self.output_path);
+        Ok(())
     }
 }
\ No newline at end of file